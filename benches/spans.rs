@@ -0,0 +1,116 @@
+#![cfg(feature = "alloc")]
+#![feature(test)]
+
+extern crate test;
+
+use hashsplit::algorithms::{Buzhash32, Cp32, Gear64, Rrs1};
+use hashsplit::config::DynConfig;
+use hashsplit::thin::Thinned;
+use hashsplit::{Hasher, Leveled, Memoryless};
+use test::{black_box, Bencher};
+
+fn data() -> Vec<u8> {
+    let mut seed = 0x7370_616e_7321_u64;
+
+    (0..1 << 22)
+        .map(|_| {
+            seed ^= seed << 13;
+            seed ^= seed >> 7;
+            seed ^= seed << 17;
+            (seed >> 56) as u8
+        })
+        .collect()
+}
+
+fn config<Hash: Hasher>(hasher: Hash) -> DynConfig<Hash> {
+    DynConfig::new(hasher, 13, 1 << 11, 1 << 16)
+}
+
+fn bench_spans<Hash: Hasher + Copy>(b: &mut Bencher, hasher: Hash)
+where
+    Hash::Checksum: Leveled,
+    Hash::State: Clone,
+{
+    let data = data();
+    b.bytes = data.len() as u64;
+    b.iter(|| config(hasher).spans(black_box(&data)).count());
+}
+
+fn bench_indexed_spans<Hash: Memoryless + Copy>(b: &mut Bencher, hasher: Hash)
+where
+    Hash::Checksum: Leveled,
+    Hash::State: Clone + PartialEq,
+{
+    let data = data();
+    assert!(config(hasher)
+        .indexed_spans(&data)
+        .map(|chunk| (chunk.len(), chunk.state))
+        .eq(config(hasher)
+            .spans(&data)
+            .map(|chunk| (chunk.len(), chunk.state))));
+
+    b.bytes = data.len() as u64;
+    b.iter(|| config(hasher).indexed_spans(black_box(&data)).count());
+}
+
+fn bench_thinned_spans<Hash: Memoryless + Copy + Thinned<[u8; 64]>>(b: &mut Bencher, hasher: Hash)
+where
+    Hash::Checksum: Leveled,
+    Hash::State: Clone + PartialEq,
+{
+    let data = data();
+    assert!(config(hasher)
+        .thinned_spans::<64>(&data)
+        .map(|chunk| (chunk.len(), chunk.state))
+        .eq(config(hasher)
+            .spans(&data)
+            .map(|chunk| (chunk.len(), chunk.state))));
+
+    b.bytes = data.len() as u64;
+    b.iter(|| config(hasher).thinned_spans::<64>(black_box(&data)).count());
+}
+
+#[bench]
+fn spans_cp32(b: &mut Bencher) {
+    bench_spans(b, Cp32);
+}
+
+#[bench]
+fn indexed_spans_cp32(b: &mut Bencher) {
+    bench_indexed_spans(b, Cp32);
+}
+
+#[bench]
+fn thinned_spans_cp32(b: &mut Bencher) {
+    bench_thinned_spans(b, Cp32);
+}
+
+#[bench]
+fn spans_rrs1(b: &mut Bencher) {
+    bench_spans(b, Rrs1::default());
+}
+
+#[bench]
+fn thinned_spans_rrs1(b: &mut Bencher) {
+    bench_thinned_spans(b, Rrs1::default());
+}
+
+#[bench]
+fn spans_buzhash32(b: &mut Bencher) {
+    bench_spans(b, Buzhash32::default());
+}
+
+#[bench]
+fn indexed_spans_buzhash32(b: &mut Bencher) {
+    bench_indexed_spans(b, Buzhash32::default());
+}
+
+#[bench]
+fn spans_gear64(b: &mut Bencher) {
+    bench_spans(b, Gear64);
+}
+
+#[bench]
+fn indexed_spans_gear64(b: &mut Bencher) {
+    bench_indexed_spans(b, Gear64);
+}
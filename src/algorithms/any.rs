@@ -0,0 +1,158 @@
+#[allow(unused)]
+use crate::util::*;
+use crate::{Hasher, Leveled, Memoryless, Named, Ring, WINDOW_SIZE};
+
+use super::{Bozo32, BupRollsum, Buzhash32, Cp32, Cp64, Gear64, Rabin53, Rrs1, Rrs64};
+
+/// The checksum of an [`Any`] hasher.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AnyChecksum {
+    Narrow(u32),
+    Wide(u64),
+}
+
+impl Default for AnyChecksum {
+    fn default() -> Self {
+        Self::Narrow(0)
+    }
+}
+
+impl From<u32> for AnyChecksum {
+    fn from(sum: u32) -> Self {
+        Self::Narrow(sum)
+    }
+}
+
+impl From<u64> for AnyChecksum {
+    fn from(sum: u64) -> Self {
+        Self::Wide(sum)
+    }
+}
+
+impl Leveled for AnyChecksum {
+    fn level(self) -> u32 {
+        match self {
+            Self::Narrow(sum) => sum.level(),
+            Self::Wide(sum) => sum.level(),
+        }
+    }
+}
+
+macro_rules! define_any_hasher {
+    ($($variant:ident),* $(,)?) => {
+        /// One of the named hashers of this crate, chosen at runtime.
+        ///
+        /// The window of a hasher is part of its type, so every hasher held
+        /// here uses the default one of [`WINDOW_SIZE`] bytes. Hashers with
+        /// other windows, like `Buzhash<Standard, 48>`, can only be used
+        /// directly.
+        #[derive(Clone, Copy)]
+        pub enum Any {
+            $($variant($variant),)*
+        }
+
+        /// The state of an [`Any`] hasher.
+        #[derive(Clone, Copy)]
+        pub enum AnyState {
+            /// The initial state of whichever hasher is in use.
+            Initial,
+            $($variant(<$variant as Hasher>::State),)*
+        }
+
+        impl Any {
+            /// Looks up a hasher by its [`Named::NAME`].
+            pub fn from_name(name: &str) -> Option<Self> {
+                $(
+                    if name == <$variant as Named>::NAME {
+                        return Some(Self::$variant(Default::default()));
+                    }
+                )*
+
+                None
+            }
+
+            pub fn name(&self) -> &'static str {
+                match self {
+                    $(Self::$variant(_) => <$variant as Named>::NAME,)*
+                }
+            }
+        }
+
+        // Every variant must use the default window, which `Any` shares.
+        const _: () = {
+            $(assert!(<<$variant as Hasher>::Ring as Ring>::LEN == WINDOW_SIZE);)*
+        };
+
+        impl Hasher for Any {
+            type Checksum = AnyChecksum;
+
+            type State = AnyState;
+
+            type Ring = [u8; WINDOW_SIZE];
+
+            const INITIAL_STATE: AnyState = AnyState::Initial;
+
+            fn process_byte(
+                &self,
+                state: AnyState,
+                old_byte: u8,
+                new_byte: u8,
+            ) -> (AnyChecksum, AnyState) {
+                match (self, state) {
+                    $(
+                        (Self::$variant(hasher), AnyState::$variant(state)) => {
+                            let (sum, state) = hasher.process_byte(state, old_byte, new_byte);
+
+                            (sum.into(), AnyState::$variant(state))
+                        }
+                        (Self::$variant(hasher), AnyState::Initial) => {
+                            let (sum, state) = hasher.process_byte(
+                                <$variant as Hasher>::INITIAL_STATE,
+                                old_byte,
+                                new_byte,
+                            );
+
+                            (sum.into(), AnyState::$variant(state))
+                        }
+                    )*
+                    _ => panic!("state does not belong to hasher {}", self.name()),
+                }
+            }
+
+            fn resets_at_boundary(&self) -> bool {
+                match self {
+                    $(Self::$variant(hasher) => hasher.resets_at_boundary(),)*
+                }
+            }
+        }
+
+        // `Any` forgets the bytes that leave its window only if every variant
+        // does.
+        const _: fn() = || {
+            fn memoryless<Hash: Memoryless>() {}
+            $(memoryless::<$variant>();)*
+        };
+
+        impl Memoryless for Any {}
+
+        $(
+            impl From<$variant> for Any {
+                fn from(hasher: $variant) -> Self {
+                    Self::$variant(hasher)
+                }
+            }
+        )*
+    };
+}
+
+define_any_hasher! {
+    Bozo32,
+    BupRollsum,
+    Buzhash32,
+    Cp32,
+    Cp64,
+    Gear64,
+    Rabin53,
+    Rrs1,
+    Rrs64,
+}
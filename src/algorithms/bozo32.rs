@@ -1,6 +1,6 @@
 #[allow(unused)]
 use crate::util::*;
-use crate::{Hasher, Named, WINDOW_SIZE};
+use crate::{Hasher, Memoryless, Named, WINDOW_SIZE};
 
 pub type Checksum = u32;
 
@@ -8,19 +8,6 @@ pub type State = u32;
 
 const PRIME: u32 = 65_521;
 
-// FIXME maybe `u32::wrapping_pow` will be made `const` and I can use that?
-const PRIME_POW: u32 = {
-    let mut i: usize = 0;
-    let mut pow: u32 = 1;
-
-    while i < WINDOW_SIZE {
-        pow = pow.wrapping_mul(PRIME);
-        i += 1;
-    }
-
-    pow
-};
-
 #[derive(Clone, Copy, Default)]
 pub struct Bozo32;
 
@@ -29,19 +16,41 @@ impl Hasher for Bozo32 {
 
     type State = State;
 
+    type Ring = [u8; WINDOW_SIZE];
+
     const INITIAL_STATE: State = 0;
 
     fn process_byte(&self, state: State, old_byte: u8, new_byte: u8) -> (Checksum, State) {
-        process_byte_freestanding(state, old_byte, new_byte)
+        process_byte_freestanding::<WINDOW_SIZE>(state, old_byte, new_byte)
+    }
+}
+
+impl Memoryless for Bozo32 {}
+
+/// Hashes a window of bytes from scratch, as the sum of `x_i * PRIME^(WINDOW
+/// - 1 - i)` modulo `2^32`, where `x_0` is the oldest byte in the window.
+pub const fn hash_window<const WINDOW: usize>(window: &[u8; WINDOW]) -> (Checksum, State) {
+    let mut sum: u32 = 0;
+
+    let mut i = 0;
+    while i < WINDOW {
+        sum = sum.wrapping_mul(PRIME).wrapping_add(window[i] as u32);
+        i += 1;
     }
+
+    (sum, sum)
 }
 
-pub const fn process_byte_freestanding(
+/// Slides the window by one byte, with all arithmetic modulo `2^32`.
+pub const fn process_byte_freestanding<const WINDOW: usize>(
     state: State,
     old_byte: u8,
     new_byte: u8,
 ) -> (Checksum, State) {
-    let sum = state * PRIME + new_byte as u32 - old_byte as u32 * PRIME_POW;
+    let sum = state
+        .wrapping_mul(PRIME)
+        .wrapping_add(new_byte as u32)
+        .wrapping_sub((old_byte as u32).wrapping_mul(const { PRIME.wrapping_pow(WINDOW as u32) }));
 
     (sum, sum)
 }
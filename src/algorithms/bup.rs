@@ -0,0 +1,84 @@
+#[allow(unused)]
+use crate::util::*;
+use crate::{Hasher, Memoryless, Named, WINDOW_SIZE};
+
+pub type Checksum = u32;
+
+pub type State = (u32, u32);
+
+const CHAR_OFFSET: u32 = 31;
+
+/// The rolling checksum of [bup](https://bup.github.io/), bit for bit.
+///
+/// bup splits where the low 13 bits of its digest are all ones, so the
+/// checksum is the complement of the digest. When bup goes on to count the
+/// bits of a split, it skips bit 13 and counts the ones from bit 14 up, so
+/// the checksum leaves bit 13 out and sets its top bit: its level is then the
+/// number of bits bup reports. Like bup, the hasher starts over from its
+/// initial state at every chunk boundary. bup has no minimum chunk size and
+/// caps chunks at 32 KiB, so its split points are those of
+/// `Config<BupRollsum, 13, 0, 0x80_00>`.
+///
+/// ```
+/// # #[cfg(feature = "alloc")]
+/// # fn main() {
+/// use hashsplit::algorithms::BupRollsum;
+/// use hashsplit::Config;
+///
+/// let cfg: Config<BupRollsum, 13, 0, 0x80_00> = Default::default();
+/// let data = [0; 100_000];
+///
+/// let lengths: Vec<usize> = cfg.spans(&data).map(|chunk| chunk.len()).collect();
+///
+/// assert_eq!(lengths.iter().sum::<usize>(), data.len());
+/// # }
+/// # #[cfg(not(feature = "alloc"))]
+/// # fn main() {}
+/// ```
+#[derive(Clone, Copy, Default)]
+pub struct BupRollsum;
+
+impl Hasher for BupRollsum {
+    type Checksum = Checksum;
+
+    type State = State;
+
+    type Ring = [u8; WINDOW_SIZE];
+
+    const INITIAL_STATE: State = (
+        WINDOW_SIZE as u32 * CHAR_OFFSET,
+        WINDOW_SIZE as u32 * (WINDOW_SIZE as u32 - 1) * CHAR_OFFSET,
+    );
+
+    fn process_byte(&self, state: State, old_byte: u8, new_byte: u8) -> (Checksum, State) {
+        process_byte_freestanding(state, old_byte, new_byte)
+    }
+
+    fn resets_at_boundary(&self) -> bool {
+        true
+    }
+}
+
+impl Memoryless for BupRollsum {}
+
+pub const fn process_byte_freestanding(
+    state: State,
+    old_byte: u8,
+    new_byte: u8,
+) -> (Checksum, State) {
+    let (s1, s2) = state;
+    let s1_new = s1
+        .wrapping_add(new_byte as u32)
+        .wrapping_sub(old_byte as u32);
+    let s2_new = s2
+        .wrapping_add(s1_new)
+        .wrapping_sub(WINDOW_SIZE as u32 * (old_byte as u32 + CHAR_OFFSET));
+    let digest = (s1_new << 16) | (s2_new & 0xffff);
+    let sum = !digest & 0x1fff | !digest >> 14 << 13 | 1 << 31;
+
+    (sum, (s1_new, s2_new))
+}
+
+impl Named for BupRollsum {
+    const NAME: &'static str = "bup";
+}
@@ -0,0 +1,119 @@
+#[allow(unused)]
+use crate::util::*;
+use crate::{Hasher, Memoryless, Named, WINDOW_SIZE};
+
+use core::marker::PhantomData;
+
+pub type Checksum = u32;
+
+pub type State = u32;
+
+/// A substitution table for [`Buzhash`], together with the name under which
+/// the resulting hasher is identified.
+pub trait Table {
+    const TABLE: [u32; 256];
+
+    const NAME: &'static str;
+}
+
+/// The table of [`Buzhash32`], identified as `buzhash32`.
+///
+/// Entry `i` is the upper half of output `i` of SplitMix64 started from the
+/// state 0, which [`splitmix_table`] computes. It is not the table of casync
+/// or of any other Buzhash tool, so it does not reproduce their boundaries. To
+/// chunk as another tool does, implement [`Table`] with that tool's table,
+/// under a name of its own, and use its window size.
+#[derive(Clone, Copy, Default)]
+pub struct Standard;
+
+impl Table for Standard {
+    const TABLE: [u32; 256] = splitmix_table(0);
+
+    const NAME: &'static str = "buzhash32";
+}
+
+/// A table of the upper halves of the first 256 outputs of SplitMix64,
+/// started from the state `seed`.
+pub const fn splitmix_table(seed: u64) -> [u32; 256] {
+    let mut table = [0; 256];
+    let mut state = seed;
+    let mut i = 0;
+
+    while i < 256 {
+        state = state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        table[i] = ((z ^ (z >> 31)) >> 32) as u32;
+        i += 1;
+    }
+
+    table
+}
+
+/// The cyclic-polynomial rolling hash, also known as Buzhash.
+///
+/// The initial state is the hash of a window of zero bytes, so that the state
+/// always equals the hash of the current window computed from scratch. The
+/// window is `WINDOW` bytes long.
+///
+/// ```
+/// # use hashsplit::Config;
+/// use hashsplit::algorithms::buzhash::{Buzhash, Standard};
+/// use hashsplit::algorithms::Buzhash32;
+///
+/// let cfg: Config<Buzhash32, 13, 0x01_00_00, 0x20_00_00> = Default::default();
+/// assert_eq!("HashSplit_13_buzhash32_W64_64Ki_2Mi", cfg.to_string());
+///
+/// let cfg: Config<Buzhash<Standard, 48>, 13, 0x01_00_00, 0x20_00_00> = Default::default();
+/// assert_eq!("HashSplit_13_buzhash32_W48_64Ki_2Mi", cfg.to_string());
+/// ```
+#[derive(Clone, Copy, Default)]
+pub struct Buzhash<T, const WINDOW: usize = WINDOW_SIZE>(PhantomData<T>);
+
+pub type Buzhash32 = Buzhash<Standard>;
+
+impl<T: Table, const WINDOW: usize> Hasher for Buzhash<T, WINDOW> {
+    type Checksum = Checksum;
+
+    type State = State;
+
+    type Ring = [u8; WINDOW];
+
+    const INITIAL_STATE: State = initial_state::<WINDOW>(&T::TABLE);
+
+    fn process_byte(&self, state: State, old_byte: u8, new_byte: u8) -> (Checksum, State) {
+        process_byte_freestanding::<WINDOW>(&T::TABLE, state, old_byte, new_byte)
+    }
+}
+
+impl<T: Table, const WINDOW: usize> Memoryless for Buzhash<T, WINDOW> {}
+
+pub const fn initial_state<const WINDOW: usize>(table: &[u32; 256]) -> State {
+    let mut i: usize = 0;
+    let mut state: u32 = 0;
+
+    while i < WINDOW {
+        state = state.rotate_left(1) ^ table[0];
+        i += 1;
+    }
+
+    state
+}
+
+pub const fn process_byte_freestanding<const WINDOW: usize>(
+    table: &[u32; 256],
+    state: State,
+    old_byte: u8,
+    new_byte: u8,
+) -> (Checksum, State) {
+    let sum = state.rotate_left(1)
+        ^ table[old_byte as usize].rotate_left(WINDOW as u32)
+        ^ table[new_byte as usize];
+
+    (sum, sum)
+}
+
+impl<T: Table, const WINDOW: usize> Named for Buzhash<T, WINDOW> {
+    const NAME: &'static str = T::NAME;
+}
@@ -1,6 +1,8 @@
+use super::siphash::siphash24;
+use crate::thin::Thinned;
 #[allow(unused)]
 use crate::util::*;
-use crate::{Hasher, Named};
+use crate::{Hasher, Memoryless, Named, WINDOW_SIZE};
 
 pub type Checksum = u32;
 
@@ -49,6 +51,10 @@ impl Hasher for Cp32 {
 
     type State = State;
 
+    // The contribution of a byte is rotated back into place after a multiple
+    // of 32 bytes, so the window can only be such a multiple.
+    type Ring = [u8; WINDOW_SIZE];
+
     const INITIAL_STATE: State = 0;
 
     fn process_byte(&self, state: State, old_byte: u8, new_byte: u8) -> (Checksum, State) {
@@ -56,12 +62,23 @@ impl Hasher for Cp32 {
     }
 }
 
+impl Memoryless for Cp32 {}
+
 pub const fn process_byte_freestanding(
     state: State,
     old_byte: u8,
     new_byte: u8,
 ) -> (Checksum, State) {
-    let sum = state.rotate_left(1) ^ LOOKUP[old_byte as usize] ^ LOOKUP[new_byte as usize];
+    process_byte_with_table(&LOOKUP, state, old_byte, new_byte)
+}
+
+pub const fn process_byte_with_table(
+    table: &[u32; 256],
+    state: State,
+    old_byte: u8,
+    new_byte: u8,
+) -> (Checksum, State) {
+    let sum = state.rotate_left(1) ^ table[old_byte as usize] ^ table[new_byte as usize];
 
     (sum, sum)
 }
@@ -69,3 +86,86 @@ pub const fn process_byte_freestanding(
 impl Named for Cp32 {
     const NAME: &'static str = "cp32";
 }
+
+/// The contribution of each pair of bytes is rotated once for every byte
+/// after it in the block, so the terms can be computed independently of each
+/// other and of the state.
+impl<const N: usize> Thinned<[u8; N]> for Cp32 {
+    const BLOCK_SIZE: usize = N;
+
+    fn process_block(
+        &self,
+        state: State,
+        old_block: &[u8],
+        new_block: &[u8; N],
+    ) -> (Checksum, State) {
+        assert_eq!(old_block.len(), N);
+
+        let mut mix = 0;
+        for (i, (&old_byte, &new_byte)) in old_block.iter().zip(new_block).enumerate() {
+            let term = LOOKUP[old_byte as usize] ^ LOOKUP[new_byte as usize];
+            mix ^= term.rotate_left(((N - 1 - i) % 32) as u32);
+        }
+        let sum = state.rotate_left((N % 32) as u32) ^ mix;
+
+        (sum, sum)
+    }
+}
+
+/// [`Cp32`] with a lookup table derived from a secret key, so that chunk
+/// boundaries cannot be predicted without it.
+///
+/// Entry `i` of the table is the low half of SipHash-2-4 of `i`, as a
+/// little-endian `u64`, under the key. There is deliberately no `Debug`
+/// implementation, and the name does not depend on the key.
+///
+/// Configuration identifiers therefore say that a key is used, but not which
+/// one, and [`Any`](super::Any) cannot hold this hasher. Parsing such an
+/// identifier into a `DynConfig<Any>` fails with
+/// [`ParseError::KeyRequired`](crate::config::ParseError::KeyRequired);
+/// [`Identifier`](crate::config::Identifier) reads its fields, for use with
+/// a key kept elsewhere.
+///
+/// ```
+/// use hashsplit::algorithms::cp32::KeyedCp32;
+/// use hashsplit::config::DynConfig;
+///
+/// let cfg = DynConfig::new(KeyedCp32::new([1; 16]), 8, 64, 1024);
+///
+/// assert_eq!(cfg.to_string(), "HashSplit_8_cp32keyed_W64_64_1Ki");
+/// ```
+#[derive(Clone)]
+pub struct KeyedCp32 {
+    table: [u32; 256],
+}
+
+impl KeyedCp32 {
+    pub fn new(key: [u8; 16]) -> Self {
+        let mut table = [0; 256];
+        for (i, entry) in table.iter_mut().enumerate() {
+            *entry = siphash24(&key, &(i as u64).to_le_bytes()) as u32;
+        }
+
+        Self { table }
+    }
+}
+
+impl Hasher for KeyedCp32 {
+    type Checksum = Checksum;
+
+    type State = State;
+
+    type Ring = [u8; WINDOW_SIZE];
+
+    const INITIAL_STATE: State = 0;
+
+    fn process_byte(&self, state: State, old_byte: u8, new_byte: u8) -> (Checksum, State) {
+        process_byte_with_table(&self.table, state, old_byte, new_byte)
+    }
+}
+
+impl Memoryless for KeyedCp32 {}
+
+impl Named for KeyedCp32 {
+    const NAME: &'static str = "cp32keyed";
+}
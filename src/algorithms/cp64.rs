@@ -0,0 +1,121 @@
+#[allow(unused)]
+use crate::util::*;
+use crate::{Hasher, Memoryless, Named, WINDOW_SIZE};
+
+pub type Checksum = u64;
+
+pub type State = u64;
+
+#[rustfmt::skip]
+const LOOKUP: [u64; 256] = [
+    0xf17d5e08fa8a7e33, 0xd74503bad9213c5a, 0x187cd46ff74250aa, 0x538b0b98b000cd0f,
+    0x60a485a3dc9030d4, 0x1f62273021481e90, 0x5269ac9328919cdf, 0x26ed1932100f0b2f,
+    0x3ac336e5ffef23d8, 0x6e3b07ea9a793373, 0x5358a3100af5865a, 0x6b042451f1b3af68,
+    0xc25f1e1a2f8bcc5a, 0xec9e83b7ce84cfa9, 0x60f8669517a5ecd2, 0x029e158ee30250d1,
+    0x5928867e7cef5d39, 0x1bd1e92c8e1c4733, 0xccc234a18a5a7d7c, 0xe0b575861b0cae8c,
+    0x94aaa1082df9f1f2, 0x1e74009b3f62a51c, 0x5cc3a998f9f70716, 0xc4881607ca83d0fd,
+    0xbf308b57927f5bd4, 0x32421c572e939242, 0xa91f0abd941c4f13, 0x2f75e6376eae68c3,
+    0xdc2f9e178c1622ed, 0x04404626eac13fdb, 0x9261ce4e22a429a4, 0x00a8236598bd29e7,
+    0x1be7d8c02ca88b89, 0x93e22a318add203d, 0x878b0ccc3d882a0e, 0xf970e37c012f83a0,
+    0x6a769edbc0e92515, 0x79bedd5c45c24986, 0xd116d4b0fa4d5498, 0x1756514c512375e0,
+    0x6ad97acb3feb39df, 0x09e3d49f8ac27b58, 0x86325ebb998f8d03, 0x48a2301ad05fa76c,
+    0x6b22df966acc9a3a, 0x5da4d68295cd4ef7, 0xf06e037f4baecd0c, 0x469a7feaf9b33e9d,
+    0xe4a79480840b678f, 0x819e4f695e809afe, 0xa4f0da12072c1167, 0x26ff39136c046954,
+    0xecace0d2915eea95, 0x18d2919b3fb209ee, 0x1665f9c355411011, 0x9d799c1ca30bbaf6,
+    0xe3aea281f94b51f1, 0x4575e4c6ba195672, 0x47348b6e776b0766, 0xf567b30d539aeb14,
+    0xb3508e8f6f46bf4d, 0xcc90896073b0d1ca, 0xb177ecb8fe9d53c6, 0xf088861683ab2d87,
+    0x9611e31d7860ba53, 0x4f918e6708fefbb9, 0x31895f6a7ae20caf, 0x434a3dc4e30f7641,
+    0x9cfce1e303521554, 0x23fee62b46c07ade, 0xbe453f05ca73bf8f, 0x6a5728808c1fd209,
+    0x25885bb68defe17b, 0x4da80b7ac7f21dd7, 0x9fca10df6002ba89, 0xc92eca414679538d,
+    0xe4857ade8ff1d7f6, 0x122d0b68faa625cf, 0x273972151f6cb632, 0xa8c8bac676042e2a,
+    0x15a755d37b278b4e, 0x560bb2ace5348616, 0x159c010d44cd8b80, 0x105cfc962a390af7,
+    0xf999247ef15d81fb, 0xb53245beef2b0089, 0x87248f7d6db7d61b, 0xf2d32e3041f4fb87,
+    0xbb4bc29afaaaa4e1, 0x901838f01b7914b7, 0x573aca603e03c5be, 0x204e6cca8153a6ba,
+    0x6fc789d0b6760bbf, 0x93d88b9b378f1071, 0xe1cc8aa9f7dcfebe, 0x8be793eb920e616e,
+    0x364cb5333d7b15a8, 0xa509cb38a4ad7030, 0x939ea41b2833d68c, 0xa69b79a7385c776a,
+    0xdaf322950d844153, 0x910fd3398ee8993e, 0x00d05232d6cd1dea, 0x112d846a4fb60115,
+    0x698d98089b9dcc70, 0xd163edb64150a390, 0x0c345e0aa1b8225e, 0x17ffd98a99280b55,
+    0x7182911339774e6d, 0x321506cb0b40e24e, 0x9915db69c2a33d7e, 0x9dfde07c857c23f0,
+    0x5b4b0ad3f8fc8ef7, 0xa44adff0b63e9d58, 0x9950479527a5954d, 0x6e1022186e48167d,
+    0x32e4ea7431f744bf, 0x473db5b9c00f8b97, 0x35c2e16dfd30a4d6, 0xca5b12af866ffb3e,
+    0xc10a521532d87db6, 0x37ba7df357313617, 0xff9f79112634345e, 0xee277b562902c9b0,
+    0x7f5af011a1454f33, 0x5643dd56fdb12e10, 0x573e741da83b8b20, 0xb41aacdbe72de9c1,
+    0xb513f962b1d997bf, 0xccb04c00506992d6, 0xdf97fdcef2ef78a7, 0x72fcb426e7819ff9,
+    0xd2a6c4e5060113d1, 0x4353119bf47a6276, 0x1ffda3126a8d4efd, 0xd5a00ccb818a7e3a,
+    0x08735f2490760299, 0xbfc58fd8f5d17d12, 0x1f6f6d0d1b8dacc8, 0x01bf2682497c4572,
+    0x25d985c57907db66, 0xf0147d3f20224948, 0x127e61c3d469d759, 0xaf7b35374bb0ce4f,
+    0xaa1784ba37616a6f, 0xf23edf1e7ea73b52, 0xbb0e248b45b9ebf9, 0x6a7cc229c0ff246c,
+    0x2b40e1ec8382cacd, 0x534fc7248d56dae4, 0xc8cf4ba8755393ce, 0x47c994406a79fb98,
+    0xd735443888950d74, 0x398ee5dcc26a4ac7, 0x1236e5ea823979d6, 0xa56098202c1f9883,
+    0xefef501e3b9347c9, 0x9c4767fc2f71cc25, 0x43ef3ccec9a6fac9, 0x706591b526b22173,
+    0xcdb85abdb944c2d4, 0xadf58c89d763b229, 0xf808b22bb644911c, 0x5682e990248ef032,
+    0x903e1f68b591338b, 0xb70b156a2bf90a25, 0x3408ce4c9da6ae35, 0xbf209292570f8f60,
+    0xf351a6e654a6de57, 0x655482fef6901516, 0xc16f42cb39f700f0, 0x99449679be4a06ad,
+    0xb48eea679a551377, 0xc9ad16d0046dfa6b, 0xd44756143e8788d0, 0x240eb7a79b1289d6,
+    0xc71ff242a43c4e26, 0x801f4ebd5e87a120, 0x708da0fc2b9392b4, 0x3241299a08a7cec4,
+    0xe3b8e5134fbf9c60, 0x47458640d5959da0, 0xa01041c0012c6ad5, 0x111c76e1d497750c,
+    0xf1c4536bb71f7031, 0x35e9a68075ddf5eb, 0x33a32cc041b47f9c, 0x5b8defff3c045ce6,
+    0x2f691cf095c6746a, 0x3546d59b5ad03437, 0x7fdab904944015dd, 0x55553b09c3f2e6b7,
+    0x0681ac0d8ce53217, 0x35cb84ebfb5b1769, 0x896c9f70d72b6438, 0x5a8bfaf6cca3d2a1,
+    0x07614804a9d9968b, 0x5e8f7c828a3564c1, 0x6a659177a5acae41, 0xb07447c9754630e8,
+    0x94d337f9ef1e53cd, 0x75a5517a5429ffc8, 0x4e586e6ed41d6106, 0xe018163b02eb4101,
+    0x9032b0c3bf1c8418, 0x547944402b9c9fcb, 0x08fb1d9565d992f7, 0x99af10e8a25668ca,
+    0xa9c4eb756948a7c7, 0x5462cbdafb2278f2, 0x22686c64920fde83, 0x814c71e2f3b59d34,
+    0xda7e6917e582b7d9, 0x56971a322ebe6c5b, 0xafc468a7bfe9f528, 0x7a77216140a27fc2,
+    0x00f742b1f779dd43, 0x0efdc08ad944f89d, 0x40a40aceb57ab6d8, 0xec28df78f7a296df,
+    0xffdbc6254196acf9, 0xe506c9a29869ea2a, 0x78f306f052e3461f, 0xef76ce3f1da2ba27,
+    0xa6c8dbb2f42e260f, 0x20fa8e711ce70ecc, 0xdf138cea99083452, 0xdd43ca4f1be1aaeb,
+    0x1d575eb1ad7088fb, 0x3078daf5258f7ece, 0x1d33931557068ea7, 0xfc8cd94afc6ff62e,
+    0x30dda45bd1dc8636, 0xc5e33a3b3691588a, 0x199e64bd17bad884, 0xf272fcc89f35d039,
+    0x13d0191399de10bd, 0xf8457511621a91f7, 0xa546e2111c12df85, 0xb9c30fd3323ad87c,
+    0x722030b06ccc9ace, 0xd5a51b870f98d38f, 0x0e20013c57af21ca, 0x1e12adc9de978b7b,
+    0xa2d102e0e53a8ed4, 0xcf0d8902f641bb02, 0x08aefde30c67ce6d, 0xca7f171b057ac038,
+    0x7c43b9d49c966610, 0x7a76c20feec0d78c, 0x2e6876af8801d22d, 0x29b75ee4f7c4c3d0,
+    0x4b6af171c9299d44, 0xd8e4077658fdead0, 0x05b0006e0f7bfe35, 0x63cdd8c378600584,
+];
+
+/// The 64-bit counterpart of [`Cp32`](super::Cp32), whose checksums have up
+/// to 64 levels.
+///
+/// ```
+/// # use hashsplit::Config;
+/// use hashsplit::algorithms::Cp64;
+///
+/// let cfg: Config<Cp64, 40, 0x01_00_00, 0x20_00_00> = Default::default();
+///
+/// assert_eq!("HashSplit_40_cp64_W64_64Ki_2Mi", cfg.to_string());
+/// ```
+#[derive(Clone, Copy, Default)]
+pub struct Cp64;
+
+impl Hasher for Cp64 {
+    type Checksum = Checksum;
+
+    type State = State;
+
+    // The contribution of a byte is rotated back into place after a multiple
+    // of 64 bytes, so the window can only be such a multiple.
+    type Ring = [u8; WINDOW_SIZE];
+
+    const INITIAL_STATE: State = 0;
+
+    fn process_byte(&self, state: State, old_byte: u8, new_byte: u8) -> (Checksum, State) {
+        process_byte_freestanding(state, old_byte, new_byte)
+    }
+}
+
+impl Memoryless for Cp64 {}
+
+pub const fn process_byte_freestanding(
+    state: State,
+    old_byte: u8,
+    new_byte: u8,
+) -> (Checksum, State) {
+    let sum = state.rotate_left(1) ^ LOOKUP[old_byte as usize] ^ LOOKUP[new_byte as usize];
+
+    (sum, sum)
+}
+
+impl Named for Cp64 {
+    const NAME: &'static str = "cp64";
+}
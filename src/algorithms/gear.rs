@@ -0,0 +1,113 @@
+#[allow(unused)]
+use crate::util::*;
+use crate::{Hasher, Memoryless, Named, Windowless};
+
+pub type Checksum = u64;
+
+pub type State = u64;
+
+#[rustfmt::skip]
+const GEAR: [u64; 256] = [
+    0xc0b34a1bc2b15f22, 0x5fbd67f450261632, 0xe07afe3ef2034c75, 0x3ca364bf86b9f5dc,
+    0xd90377cf1ce76042, 0xd795753d4cb2b5cc, 0x32cc9a4b8df0ecde, 0xb0c29d02865e0ef4,
+    0x57137ac6908a4963, 0xb86a9dc8fa8ef97c, 0x25ba1f7857f196a7, 0x7f49676ec88722ce,
+    0xe1ebe4ad6cfad8d3, 0x09754d643a2a7485, 0x5a7f23ae7e78689f, 0xb45346f23ea33832,
+    0x880be6391d8f3439, 0xf5d573cfeecd0bb7, 0x933856f2e689e555, 0x4e25faa4773fb9af,
+    0xc68ea0ef3bfd0b73, 0x21e4a5f4b97eae13, 0x6cfbdc0a66899586, 0xe6564652d0fa2b80,
+    0x7348ede4d6c17220, 0xf19a30ce4b7a0cee, 0x65418b6f6fbdea53, 0x9f57b305fea59c14,
+    0xae47e153e7b819e0, 0x4b0be8da70719075, 0xe9b5c68623473ee7, 0x3c76bc9134a2d2b3,
+    0x56b133adadb7107c, 0xba4465403ca92a6d, 0x048ff44920ab145f, 0x0f7cfdec986e8804,
+    0x21bdcd51afb5d1a9, 0xef03fa6b09f73cfb, 0x779de0786724e22b, 0x9f641a105b423fe6,
+    0x7c4b9f601e21fe06, 0x3cc2e4ea238bccce, 0x2aef108d397cf639, 0x5570ab88b75bc15c,
+    0x22c3dcaa9e1b91f5, 0xe153d845e8d09036, 0x9535ba772ddce11f, 0x368636f53875a2e6,
+    0x97bab76da26ad282, 0xd48a8cdd92bab474, 0x19a91153db1e7f87, 0x81ac5ba8fe2dadfd,
+    0x8aa6931a5d5a8868, 0x57c186f138d9ca7c, 0x6d7556378da81df8, 0x6210eb0c40ad0062,
+    0xcd2cc109324aae30, 0x3c9c7d1c5335d8de, 0xb48907b0aaaeb159, 0x4f011e288442dd30,
+    0x5377f4b3e6e9d354, 0xe7f51faddc602de6, 0x92bc5f0727a0e661, 0xa77ba3ccb3d0d6c0,
+    0x51c4658f82e757e0, 0xb9820fda5151bff9, 0xc9769b6926d1015d, 0xfaf19cdca8fa3f87,
+    0xa4edcafa53d1bc28, 0x252b8f0c0f331fa7, 0x6247cfd5f0748c68, 0xeeaedf883d745e1b,
+    0x19c6d70bd1e3ea85, 0x5e6f56670bffbd83, 0xcee6713366109151, 0x5c7a1ce04282cf28,
+    0x62c305e6b9828b59, 0x2aa1f96b20fd54dd, 0xb84963cb3add54b4, 0x404c624335f10f4b,
+    0xd7fa15d099173eec, 0x9a0b6e7ae3d7fcff, 0xc906cb16d1e1e584, 0x00dd4f571bb91e51,
+    0x8bb3f895d345a241, 0xcc2ea2eebfaa0ce5, 0xef141fd4d4de6f38, 0x62d76da656277412,
+    0xa2996cf635456faa, 0x42c2f327f227dcc1, 0x66b7578a2785b743, 0x93a98511d35a30fa,
+    0x4d45b415ae9a5729, 0x5e1208143ce5d89c, 0xa8d5a57145af9267, 0x240e1c959d958e06,
+    0x9823f5e49775c355, 0x4c56022fd2b97d1a, 0xcbd3074c4af5c4d1, 0x772b6339d6e3b754,
+    0x04ef03c81b49439a, 0x82a1fe458d22af43, 0x918721b6f3b6a505, 0x0b6af5a0321b074b,
+    0x0feb24a19515ca88, 0x576e7f044792569c, 0x5c93bde8aa2266ee, 0xdd5f1ddbe9557eb4,
+    0xf31b79e01330c98e, 0x78d644f1f2dce44b, 0x433592d7d198c1be, 0x4bd47ac864f032a3,
+    0xa83fd76a617223b3, 0x67ddcaeb0c2d5e4f, 0x98392f695ea8ca27, 0x50cf1e7a77990fd8,
+    0x34735c620152ba11, 0x540550737406db1c, 0x7d605aa94f1bedb9, 0x3ada8b3b8fcca99b,
+    0xbe1ec6f20b72640c, 0x16d88012b5a796b9, 0x1b60423269e430d8, 0x84ffd3c84f8b6f49,
+    0x04819e1dfbe14bad, 0x43055aaf6a7ef660, 0xc6f503f3c15f685f, 0x7be276fae6df6bae,
+    0x788aa57acd4d900c, 0x2bff5c9a5b59aca1, 0xc42d9ce18bcfcc5a, 0x499fc27f9eebc7b1,
+    0x93e1b228eccb0d21, 0xefce1ea91638b5fe, 0x591bea4258ed55f8, 0xc1a1f92b72f386ec,
+    0x4d5388c426f3c68f, 0x21d89d006f987f24, 0x435d0017f463ba4a, 0xa472305b5e001b91,
+    0xea474ec7bcf28dcb, 0x7054bcff706cca42, 0xaa136b119c9be60f, 0x6e632499ab7e578c,
+    0x1fe1819514aab0a0, 0x37d204e9153b7787, 0xa074d3b74a8d049d, 0xc61f848d22d9256c,
+    0xb40f267449ec6f70, 0x4cb62071118f391c, 0xd73d313c13d5925c, 0x28775423b78e9791,
+    0x50fe18d6a1bcc570, 0xbc11fc4f43972ac5, 0x9625304d9189912a, 0x6205cdf59010f711,
+    0xb9483293b463d8c2, 0x4e52a7a7257a0aa6, 0xb0caddda0444e10d, 0x1e69df82616ac028,
+    0x276e38fe04b6cef6, 0x59839e3d0da64919, 0xfa5fbdd9438ab9d4, 0xcdc5dacc28d73c80,
+    0xcff9412e4ae7dfb3, 0x7aea7bee32d2fcef, 0xc192acafb48c2a9c, 0x1aaf5e844ece6e43,
+    0xbfe42f717b41406b, 0x7324b76b08e610af, 0x38a7ee0d80db754b, 0xf866d9429557ac72,
+    0x322ad942475c7735, 0x8cf03ca138b175fa, 0xf0fea65e40982dfe, 0xaa6aea1f965ab5f0,
+    0xa9a624272a504a69, 0x2afb1c2bb88f7cad, 0xec6ee5bde1e962ac, 0x1b7cedbe5262abbc,
+    0x0f362d4f6a4567fc, 0x9edbce70ccb43834, 0x402712c873db87e2, 0x5bb378db799b2482,
+    0x4ebc8c13e0115dfa, 0xfaf832b79f8843e8, 0x8e1fa0fdd710b242, 0x50a445733a861bc7,
+    0xdf9ac7462ad0dd81, 0xd1c1c336662513b1, 0xd759831a732ea18d, 0xcb3c2e19828f4ec9,
+    0xd13e02927e88d018, 0x20c8a828b9e1f169, 0x35a5854108e7cdb0, 0x537f3572483871d8,
+    0x6a5166f05b94160a, 0xca38ffb8d0de7072, 0xf7ca13ef5a8803e1, 0xfbaf783d4e7c76ce,
+    0xa18533e31b3862a6, 0x90d43e3392713d14, 0xf24a81325597d26e, 0x839e5207d6451b1f,
+    0xd0400be5910d71a5, 0x4dac89771c40c869, 0x2ddbff4eb655a65a, 0x6c48a9ee805f846e,
+    0x5f5e80e06233a852, 0x4b0b295be5836451, 0x5b420f606e0b9ade, 0x3ecd0c1b1253edbf,
+    0x773e5a3d758f2f90, 0x9f4e2a2499e8ee46, 0x82bd2da0d896ab96, 0x27fcd44a54979e77,
+    0xb28fd4c886e07f81, 0xda5eb8388fcfe7c8, 0x426531216c70bc32, 0xaed8ad0497fedcbc,
+    0xb7aff90b22d621f9, 0x620a07ed084b02ff, 0xd52d1b8aecc9e74b, 0xe385420d5f0a585c,
+    0x4adecaf7144df1b3, 0x5ebf837af9f88933, 0x4af80d4b0f6f4841, 0x445063b98fed34bd,
+    0xd0dff054c47ad8e6, 0x27415fabaec2d412, 0xbd267248fef97d69, 0x5753bbb4b36fc66c,
+    0x1c6eb202cc9a85e2, 0x41569fc52507f88c, 0xac12e93cb4800fc0, 0x2eb135a10c148614,
+    0x3836a4473a48f69f, 0x117655a7e3625bf7, 0x827fa64d9cfade59, 0x8720d9ba71087b5d,
+    0x899fa2ccf951bc5f, 0x17ff5d2fd6077f00, 0x0cf2d1a6346de8cf, 0x2360721d6544e91d,
+    0x90f78f34e7080d04, 0x7690f2d41c5b7a42, 0x763136259f525a6b, 0x8bf42eda687fa0ba,
+    0xd425637c8446f545, 0x286aee36308092b0, 0xea50e76d449b9d29, 0x9ea5aac436f774fe,
+    0x351e32454e71fb4b, 0x494c1e86384b6234, 0x18fb8a3545e559aa, 0x7817fe156972d901,
+];
+
+/// The Gear rolling hash used by FastCDC.
+///
+/// Each byte is shifted out of the state after 64 steps, so the window is
+/// implicit and `old_byte` is ignored. Since the high bits of the state are
+/// the ones that depend on the whole window, the checksum is the state with
+/// its bits reversed.
+#[derive(Clone, Copy, Default)]
+pub struct Gear64;
+
+impl Hasher for Gear64 {
+    type Checksum = Checksum;
+
+    type State = State;
+
+    // A byte is shifted out of the state 64 bytes after it arrives.
+    type Ring = [u8; 64];
+
+    const INITIAL_STATE: State = 0;
+
+    fn process_byte(&self, state: State, _old_byte: u8, new_byte: u8) -> (Checksum, State) {
+        process_byte_freestanding(state, new_byte)
+    }
+}
+
+impl Memoryless for Gear64 {}
+
+impl Windowless for Gear64 {}
+
+pub const fn process_byte_freestanding(state: State, new_byte: u8) -> (Checksum, State) {
+    let new_state = (state << 1).wrapping_add(GEAR[new_byte as usize]);
+
+    (new_state.reverse_bits(), new_state)
+}
+
+impl Named for Gear64 {
+    const NAME: &'static str = "gear64";
+}
@@ -1,7 +1,22 @@
+pub mod any;
 pub mod bozo32;
+pub mod bup;
+pub mod buzhash;
 pub mod cp32;
+pub mod cp64;
+pub mod gear;
+pub mod rabin;
 pub mod rrs;
+pub mod rrs64;
+mod siphash;
 
+pub use any::Any;
 pub use bozo32::Bozo32;
-pub use cp32::Cp32;
+pub use bup::BupRollsum;
+pub use buzhash::Buzhash32;
+pub use cp32::{Cp32, KeyedCp32};
+pub use cp64::Cp64;
+pub use gear::Gear64;
+pub use rabin::{Rabin, Rabin53};
 pub use rrs::Rrs1;
+pub use rrs64::Rrs64;
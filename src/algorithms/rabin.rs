@@ -0,0 +1,235 @@
+#[allow(unused)]
+use crate::util::*;
+use crate::{Hasher, Memoryless, Named, WINDOW_SIZE};
+
+pub type Checksum = u64;
+
+pub type State = u64;
+
+/// The degree of a polynomial over GF(2), represented as a bit vector.
+pub const fn degree(poly: u64) -> u32 {
+    63 - poly.leading_zeros()
+}
+
+const fn reduce(mut value: u128, poly: u64) -> u64 {
+    let deg = degree(poly);
+
+    while value >> deg != 0 {
+        let shift = 127 - value.leading_zeros() - deg;
+        value ^= (poly as u128) << shift;
+    }
+
+    value as u64
+}
+
+/// The product of two polynomials over GF(2).
+const fn multiply(a: u64, b: u64) -> u128 {
+    let mut product = 0;
+    let mut i = 0;
+
+    while i < 64 {
+        if b >> i & 1 != 0 {
+            product ^= (a as u128) << i;
+        }
+        i += 1;
+    }
+
+    product
+}
+
+const fn gcd(mut a: u64, mut b: u64) -> u64 {
+    while b != 0 {
+        let rest = reduce(a as u128, b);
+        a = b;
+        b = rest;
+    }
+
+    a
+}
+
+/// Whether a polynomial over GF(2) is irreducible, by Ben-Or's test: a
+/// polynomial of degree `d` has no factor of degree `i` exactly when it is
+/// coprime with `x^(2^i) - x`, and it is enough to check every `i` up to
+/// `d / 2`.
+///
+/// ```
+/// use hashsplit::algorithms::rabin::is_irreducible;
+///
+/// assert!(is_irreducible(0x11b)); // x^8 + x^4 + x^3 + x + 1, as in AES
+/// assert!(is_irreducible(0x8000_0000_0000_0003)); // x^63 + x + 1
+/// assert!(is_irreducible(0xbfe6_b8a5_bf37_8d83)); // used by LBFS
+/// assert!(!is_irreducible(0x105)); // (x^4 + x + 1)^2
+/// assert!(!is_irreducible(0x1ff)); // has the root 1
+/// ```
+pub const fn is_irreducible(poly: u64) -> bool {
+    if poly < 2 {
+        return false;
+    }
+
+    // `x^(2^i) mod poly`, starting from `x`.
+    let mut power = 0b10;
+    let mut i = 1;
+
+    while i <= degree(poly) / 2 {
+        power = reduce(multiply(power, power), poly);
+        if gcd(poly, power ^ 0b10) != 1 {
+            return false;
+        }
+        i += 1;
+    }
+
+    true
+}
+
+/// The table used to reduce the byte shifted out of the fingerprint when a new
+/// byte is pushed.
+pub const fn push_table(poly: u64) -> [u64; 256] {
+    let deg = degree(poly);
+    assert!(deg >= 8, "the polynomial must have degree at least 8");
+    assert!(is_irreducible(poly), "the polynomial must be irreducible");
+
+    let mut table = [0; 256];
+    let mut i = 0;
+
+    while i < 256 {
+        table[i] = reduce((i as u128) << deg, poly);
+        i += 1;
+    }
+
+    table
+}
+
+/// The table giving the contribution of a byte leaving the window, that is,
+/// `byte * x^(8 * (window - 1)) mod poly`.
+pub const fn pop_table(poly: u64, push: &[u64; 256], window: usize) -> [u64; 256] {
+    let mut table = [0; 256];
+    let mut i = 0;
+
+    while i < 256 {
+        let mut fingerprint = i as u64;
+        let mut j = 1;
+
+        while j < window {
+            fingerprint = push_byte(poly, push, fingerprint, 0);
+            j += 1;
+        }
+
+        table[i] = fingerprint;
+        i += 1;
+    }
+
+    table
+}
+
+const fn push_byte(poly: u64, push: &[u64; 256], fingerprint: u64, byte: u8) -> u64 {
+    let shift = degree(poly) - 8;
+    let top = fingerprint >> shift;
+    let rest = fingerprint & ((1 << shift) - 1);
+
+    ((rest << 8) | byte as u64) ^ push[top as usize]
+}
+
+const NAME_PREFIX: &[u8] = b"rabin0x";
+
+const NAME_CAPACITY: usize = NAME_PREFIX.len() + 16;
+
+const fn name_bytes(poly: u64) -> ([u8; NAME_CAPACITY], usize) {
+    let mut bytes = [0; NAME_CAPACITY];
+    let mut len = 0;
+
+    while len < NAME_PREFIX.len() {
+        bytes[len] = NAME_PREFIX[len];
+        len += 1;
+    }
+
+    let mut shift = (degree(poly) / 4 * 4) as i32;
+    while shift >= 0 {
+        let digit = ((poly >> shift) & 0xf) as u8;
+        bytes[len] = if digit < 10 {
+            b'0' + digit
+        } else {
+            b'a' + digit - 10
+        };
+        len += 1;
+        shift -= 4;
+    }
+
+    (bytes, len)
+}
+
+/// A Rabin fingerprint over GF(2), modulo the polynomial `POLY`.
+///
+/// `POLY` must be irreducible and of degree between 8 and 63, which is checked
+/// at compile time when the hasher is used; it is written as a bit vector, with
+/// bit `i` the coefficient of `x^i`. The fingerprint of the window is the
+/// window's bytes read as a polynomial, most significant byte first, reduced
+/// modulo `POLY`. This is the fingerprint of LBFS, which uses the polynomial
+/// `0xbfe6b8a5bf378d83` and a window of 48 bytes.
+///
+/// Only [`Rabin53`] is one of the hashers of [`Any`](super::Any), so the
+/// identifiers of configurations with other polynomials cannot be parsed.
+///
+/// ```
+/// # use hashsplit::Config;
+/// use hashsplit::algorithms::Rabin;
+///
+/// let cfg: Config<Rabin<0x3d_a3_35_8b_4d_c1_73>, 20, 0x08_00_00, 0x80_00_00> = Default::default();
+///
+/// assert_eq!("HashSplit_20_rabin0x3da3358b4dc173_W64_512Ki_8Mi", cfg.to_string());
+/// ```
+#[derive(Clone, Copy, Default)]
+pub struct Rabin<const POLY: u64, const WINDOW: usize = WINDOW_SIZE>;
+
+/// [`Rabin`] over an irreducible polynomial of degree 53, with a window of 64
+/// bytes.
+///
+/// It does not find restic's boundaries, even with the same polynomial:
+/// restic restarts the fingerprint at every chunk and skips the first bytes of
+/// each.
+pub type Rabin53 = Rabin<0x3d_a3_35_8b_4d_c1_73>;
+
+impl<const POLY: u64, const WINDOW: usize> Rabin<POLY, WINDOW> {
+    pub const PUSH: [u64; 256] = push_table(POLY);
+
+    pub const POP: [u64; 256] = pop_table(POLY, &Self::PUSH, WINDOW);
+
+    const NAME_BYTES: ([u8; NAME_CAPACITY], usize) = name_bytes(POLY);
+}
+
+impl<const POLY: u64, const WINDOW: usize> Hasher for Rabin<POLY, WINDOW> {
+    type Checksum = Checksum;
+
+    type State = State;
+
+    type Ring = [u8; WINDOW];
+
+    const INITIAL_STATE: State = 0;
+
+    fn process_byte(&self, state: State, old_byte: u8, new_byte: u8) -> (Checksum, State) {
+        process_byte_freestanding::<POLY, WINDOW>(state, old_byte, new_byte)
+    }
+}
+
+impl<const POLY: u64, const WINDOW: usize> Memoryless for Rabin<POLY, WINDOW> {}
+
+pub const fn process_byte_freestanding<const POLY: u64, const WINDOW: usize>(
+    state: State,
+    old_byte: u8,
+    new_byte: u8,
+) -> (Checksum, State) {
+    let popped = state ^ Rabin::<POLY, WINDOW>::POP[old_byte as usize];
+    let sum = push_byte(POLY, &Rabin::<POLY, WINDOW>::PUSH, popped, new_byte);
+
+    (sum, sum)
+}
+
+impl<const POLY: u64, const WINDOW: usize> Named for Rabin<POLY, WINDOW> {
+    const NAME: &'static str = {
+        let (bytes, _) = Self::NAME_BYTES.0.split_at(Self::NAME_BYTES.1);
+
+        match core::str::from_utf8(bytes) {
+            Ok(name) => name,
+            Err(_) => panic!("the name of a Rabin hasher is ASCII"),
+        }
+    };
+}
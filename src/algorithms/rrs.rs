@@ -1,41 +1,154 @@
+use crate::thin::Thinned;
 #[allow(unused)]
 use crate::util::*;
-use crate::{Hasher, Named, WINDOW_SIZE};
+use crate::{Hasher, Memoryless, Named, WINDOW_SIZE};
 
 pub type Checksum = u32;
 
 pub type State = (u32, u32);
 
 #[derive(Clone, Copy, Default)]
-pub struct Rrs<const MODULUS: u32, const OFFSET: u32>;
+pub struct Rrs<const MODULUS: u32, const OFFSET: u32, const WINDOW: usize = WINDOW_SIZE>;
 
-impl<const MODULUS: u32, const OFFSET: u32> Hasher for Rrs<MODULUS, OFFSET> {
+impl<const MODULUS: u32, const OFFSET: u32, const WINDOW: usize> Hasher
+    for Rrs<MODULUS, OFFSET, WINDOW>
+{
     type Checksum = Checksum;
 
     type State = State;
 
-    const INITIAL_STATE: State = (0, 0);
+    type Ring = [u8; WINDOW];
+
+    // The hash of the zeros that fill the window before the input begins, so
+    // that the state is always that of the bytes in the window.
+    const INITIAL_STATE: State = hash_window::<MODULUS, OFFSET, WINDOW>(&[0; WINDOW]).1;
 
     fn process_byte(&self, state: State, old_byte: u8, new_byte: u8) -> (Checksum, State) {
-        process_byte_freestanding::<MODULUS, OFFSET>(state, old_byte, new_byte)
+        process_byte_freestanding::<MODULUS, OFFSET, WINDOW>(state, old_byte, new_byte)
     }
 }
 
-pub const fn process_byte_freestanding<const MODULUS: u32, const OFFSET: u32>(
+impl<const MODULUS: u32, const OFFSET: u32, const WINDOW: usize> Memoryless
+    for Rrs<MODULUS, OFFSET, WINDOW>
+{
+}
+
+const fn checksum(a: u32, b: u32) -> Checksum {
+    b.wrapping_add(a << 16)
+}
+
+/// Hashes a window of bytes from scratch, following the definition in the
+/// specification:
+///
+/// ```text
+/// a = sum of (x_i + OFFSET) mod MODULUS
+/// b = sum of (WINDOW - i) * (x_i + OFFSET) mod MODULUS
+/// s = b + 2^16 * a
+/// ```
+///
+/// where `x_0` is the oldest byte in the window.
+pub const fn hash_window<const MODULUS: u32, const OFFSET: u32, const WINDOW: usize>(
+    window: &[u8; WINDOW],
+) -> (Checksum, State) {
+    let modulus = MODULUS as u64;
+    let mut a: u64 = 0;
+    let mut b: u64 = 0;
+
+    let mut i = 0;
+    while i < WINDOW {
+        let term = (window[i] as u64 + OFFSET as u64) % modulus;
+        a = (a + term) % modulus;
+        b = (b + (WINDOW - i) as u64 % modulus * term) % modulus;
+        i += 1;
+    }
+
+    let (a, b) = (a as u32, b as u32);
+
+    (checksum(a, b), (a, b))
+}
+
+/// Slides the window by one byte.
+///
+/// The arithmetic is done modulo `MODULUS` throughout, so no input can
+/// overflow it.
+pub const fn process_byte_freestanding<
+    const MODULUS: u32,
+    const OFFSET: u32,
+    const WINDOW: usize,
+>(
     state: State,
     old_byte: u8,
     new_byte: u8,
 ) -> (Checksum, State) {
-    let (a, b) = state;
-    let a_new = (a - old_byte as u32 + new_byte as u32) % MODULUS;
-    let b_new = (b - WINDOW_SIZE as u32 * (old_byte as u32 + OFFSET) + a_new) % MODULUS;
-    let new_state = (a_new, b_new);
-    let sum = b_new + (a_new << 16);
+    let modulus = MODULUS as u64;
+    let (a, b) = (state.0 as u64, state.1 as u64);
+    let old_byte = old_byte as u64 % modulus;
+    let new_byte = new_byte as u64 % modulus;
+
+    let a_new = (a + modulus - old_byte + new_byte) % modulus;
+    let leaving = WINDOW as u64 % modulus * ((old_byte + OFFSET as u64) % modulus) % modulus;
+    let b_new = (b + modulus - leaving + a_new) % modulus;
+
+    let (a_new, b_new) = (a_new as u32, b_new as u32);
+
+    (checksum(a_new, b_new), (a_new, b_new))
+}
+
+/// With `d_i = new_i - old_i` for the bytes of the block, numbered from 1, a
+/// block of `N` bytes adds `sum d_i` to `a`, and
+/// `N * a + sum (N + 1 - i) * d_i - WINDOW * sum (old_i + OFFSET)` to `b`.
+impl<const MODULUS: u32, const OFFSET: u32, const WINDOW: usize, const N: usize> Thinned<[u8; N]>
+    for Rrs<MODULUS, OFFSET, WINDOW>
+{
+    const BLOCK_SIZE: usize = N;
 
-    (sum, new_state)
+    fn process_block(
+        &self,
+        state: State,
+        old_block: &[u8],
+        new_block: &[u8; N],
+    ) -> (Checksum, State) {
+        assert_eq!(old_block.len(), N);
+
+        // The sums of the positive and negative terms are kept apart. They
+        // cannot overflow for blocks of up to `2^24` bytes.
+        const { assert!(N <= 1 << 24, "the block is too large") };
+        let (mut new_sum, mut old_sum) = (0u64, 0u64);
+        let (mut new_weighted, mut old_weighted) = (0u64, 0u64);
+        for (i, (&old_byte, &new_byte)) in old_block.iter().zip(new_block).enumerate() {
+            let weight = (N - i) as u64;
+            new_sum += new_byte as u64;
+            old_sum += old_byte as u64;
+            new_weighted += weight * new_byte as u64;
+            old_weighted += weight * old_byte as u64;
+        }
+
+        let modulus = MODULUS as u128;
+        let (new_sum, old_sum) = (new_sum as u128, old_sum as u128);
+        let (new_weighted, old_weighted) = (new_weighted as u128, old_weighted as u128);
+        let (a, b) = (state.0 as u128, state.1 as u128);
+        let a_new = (a + new_sum % modulus + modulus - old_sum % modulus) % modulus;
+        let leaving =
+            WINDOW as u128 % modulus * ((old_sum + N as u128 * OFFSET as u128) % modulus) % modulus;
+        let b_new = (b + N as u128 % modulus * a % modulus + new_weighted % modulus + 2 * modulus
+            - old_weighted % modulus
+            - leaving)
+            % modulus;
+
+        let (a_new, b_new) = (a_new as u32, b_new as u32);
+
+        (checksum(a_new, b_new), (a_new, b_new))
+    }
 }
 
-pub type Rrs1 = Rrs<25_536, 31>;
+/// RRS1 as defined by the specification, with a modulus of `2^16` and an
+/// offset of 31, the same as bup's rollsum.
+///
+/// Earlier versions of this crate used a modulus of 25_536 and started from a
+/// state of `(0, 0)`, so the boundaries they found differ from the ones found
+/// now. Their identifiers have no window size, and parsing them fails with
+/// [`ParseError::Outdated`](crate::config::ParseError::Outdated).
+pub type Rrs1 = Rrs<65_536, 31>;
 
 impl Named for Rrs1 {
     const NAME: &'static str = "RRS1";
@@ -0,0 +1,98 @@
+#[allow(unused)]
+use crate::util::*;
+use crate::{Hasher, Memoryless, Named, WINDOW_SIZE};
+
+pub type Checksum = u64;
+
+pub type State = (u64, u64);
+
+/// The 64-bit counterpart of [`Rrs`](super::rrs::Rrs), whose checksum is
+/// `b + 2^32 * a`.
+#[derive(Clone, Copy, Default)]
+pub struct WideRrs<const MODULUS: u64, const OFFSET: u64, const WINDOW: usize = WINDOW_SIZE>;
+
+impl<const MODULUS: u64, const OFFSET: u64, const WINDOW: usize> Hasher
+    for WideRrs<MODULUS, OFFSET, WINDOW>
+{
+    type Checksum = Checksum;
+
+    type State = State;
+
+    type Ring = [u8; WINDOW];
+
+    const INITIAL_STATE: State = hash_window::<MODULUS, OFFSET, WINDOW>(&[0; WINDOW]).1;
+
+    fn process_byte(&self, state: State, old_byte: u8, new_byte: u8) -> (Checksum, State) {
+        process_byte_freestanding::<MODULUS, OFFSET, WINDOW>(state, old_byte, new_byte)
+    }
+}
+
+impl<const MODULUS: u64, const OFFSET: u64, const WINDOW: usize> Memoryless
+    for WideRrs<MODULUS, OFFSET, WINDOW>
+{
+}
+
+const fn checksum(a: u64, b: u64) -> Checksum {
+    b.wrapping_add(a << 32)
+}
+
+/// Hashes a window of bytes from scratch, as
+/// [`rrs::hash_window`](super::rrs::hash_window) does with `2^32` in place of
+/// `2^16`.
+pub const fn hash_window<const MODULUS: u64, const OFFSET: u64, const WINDOW: usize>(
+    window: &[u8; WINDOW],
+) -> (Checksum, State) {
+    let modulus = MODULUS as u128;
+    let mut a: u128 = 0;
+    let mut b: u128 = 0;
+
+    let mut i = 0;
+    while i < WINDOW {
+        let term = (window[i] as u128 + OFFSET as u128) % modulus;
+        a = (a + term) % modulus;
+        b = (b + (WINDOW - i) as u128 % modulus * term) % modulus;
+        i += 1;
+    }
+
+    let (a, b) = (a as u64, b as u64);
+
+    (checksum(a, b), (a, b))
+}
+
+/// Slides the window by one byte, with all arithmetic modulo `MODULUS`.
+pub const fn process_byte_freestanding<
+    const MODULUS: u64,
+    const OFFSET: u64,
+    const WINDOW: usize,
+>(
+    state: State,
+    old_byte: u8,
+    new_byte: u8,
+) -> (Checksum, State) {
+    let modulus = MODULUS as u128;
+    let (a, b) = (state.0 as u128, state.1 as u128);
+    let old_byte = old_byte as u128 % modulus;
+    let new_byte = new_byte as u128 % modulus;
+
+    let a_new = (a + modulus - old_byte + new_byte) % modulus;
+    let leaving = WINDOW as u128 % modulus * ((old_byte + OFFSET as u128) % modulus) % modulus;
+    let b_new = (b + modulus - leaving + a_new) % modulus;
+
+    let (a_new, b_new) = (a_new as u64, b_new as u64);
+
+    (checksum(a_new, b_new), (a_new, b_new))
+}
+
+pub type Rrs64 = WideRrs<{ 1 << 32 }, 31>;
+
+/// ```
+/// # use hashsplit::Config;
+/// use hashsplit::algorithms::Rrs64;
+///
+/// let cfg: Config<Rrs64, 40, 0x01_00_00, 0x20_00_00> = Default::default();
+///
+/// assert_eq!("HashSplit_40_RRS64_W64_64Ki_2Mi", cfg.to_string());
+/// ```
+impl Named for Rrs64 {
+    const NAME: &'static str = "RRS64";
+}
@@ -0,0 +1,59 @@
+//! SipHash-2-4, which derives the table of
+//! [`KeyedCp32`](super::KeyedCp32) from its key.
+//!
+//! This is kept out of the public API; `tests/siphash.rs` includes the file
+//! to check it against the reference vectors.
+
+/// SipHash-2-4 of `message` under `key`, as defined by Aumasson and
+/// Bernstein.
+pub(crate) fn siphash24(key: &[u8; 16], message: &[u8]) -> u64 {
+    let word = |bytes: &[u8]| {
+        let mut word = [0; 8];
+        word[..bytes.len()].copy_from_slice(bytes);
+
+        u64::from_le_bytes(word)
+    };
+    let (k0, k1) = (word(&key[..8]), word(&key[8..]));
+
+    let mut v = [
+        k0 ^ 0x736f_6d65_7073_6575,
+        k1 ^ 0x646f_7261_6e64_6f6d,
+        k0 ^ 0x6c79_6765_6e65_7261,
+        k1 ^ 0x7465_6462_7974_6573,
+    ];
+
+    fn round(v: &mut [u64; 4]) {
+        v[0] = v[0].wrapping_add(v[1]);
+        v[1] = v[1].rotate_left(13) ^ v[0];
+        v[0] = v[0].rotate_left(32);
+        v[2] = v[2].wrapping_add(v[3]);
+        v[3] = v[3].rotate_left(16) ^ v[2];
+        v[0] = v[0].wrapping_add(v[3]);
+        v[3] = v[3].rotate_left(21) ^ v[0];
+        v[2] = v[2].wrapping_add(v[1]);
+        v[1] = v[1].rotate_left(17) ^ v[2];
+        v[2] = v[2].rotate_left(32);
+    }
+
+    let mut compress = |block: u64| {
+        v[3] ^= block;
+        round(&mut v);
+        round(&mut v);
+        v[0] ^= block;
+    };
+
+    let blocks = message.chunks_exact(8);
+    let rest = blocks.remainder();
+    for block in blocks {
+        compress(word(block));
+    }
+    // The last block holds the bytes left over and the length of the message.
+    compress(word(rest) | (message.len() as u64) << 56);
+
+    v[2] ^= 0xff;
+    for _ in 0..4 {
+        round(&mut v);
+    }
+
+    v[0] ^ v[1] ^ v[2] ^ v[3]
+}
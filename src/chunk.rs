@@ -1,13 +1,18 @@
+use crate::digest::{self, Digest};
+use crate::iter::Boundary;
 #[allow(unused)]
 use crate::util::*;
 use crate::Hasher;
 
-use alloc::{borrow::Cow, boxed::Box};
+use alloc::{borrow::Cow, boxed::Box, vec::Vec};
 use core::ops::Deref;
 
 pub struct ResumableChunk<'a, Hash: Hasher> {
     chunk: Cow<'a, [u8]>,
     pub state: Hash::State,
+    /// The level of the checksum that ended the chunk, or `None` if the chunk
+    /// was capped or ended with the input.
+    pub level: Option<u32>,
 }
 
 impl<'a, Hash: Hasher> ResumableChunk<'a, Hash> {
@@ -15,6 +20,17 @@ impl<'a, Hash: Hasher> ResumableChunk<'a, Hash> {
         Self {
             chunk: data.into(),
             state,
+            level: None,
+        }
+    }
+
+    pub fn at_boundary<T: Into<Cow<'a, [u8]>>>(data: T, boundary: Boundary<Hash>) -> Self {
+        let level = boundary.level();
+
+        Self {
+            chunk: data.into(),
+            state: boundary.into_state(),
+            level,
         }
     }
 }
@@ -27,11 +43,536 @@ impl<'a, Hash: Hasher> Deref for ResumableChunk<'a, Hash> {
     }
 }
 
-pub enum TreeNode<'a, Hash: Hasher> {
-    Internal(Box<[Self]>),
-    Leaf(Box<[ResumableChunk<'a, Hash>]>),
+pub enum TreeNode<'a, Hash: Hasher, D: Digest> {
+    Internal {
+        digest: D::Output,
+        children: Box<[Self]>,
+    },
+    Leaf {
+        digest: D::Output,
+        chunks: Box<[ResumableChunk<'a, Hash>]>,
+        /// The digests of the chunks, in the same order.
+        chunk_digests: Box<[D::Output]>,
+    },
+}
+
+impl<'a, Hash: Hasher, D: Digest> TreeNode<'a, Hash, D> {
+    /// The digest of the node, computed from the digests of its children or
+    /// chunks with [`digest::internal_digest`] or [`digest::leaf_digest`].
+    pub fn digest(&self) -> &D::Output {
+        match self {
+            Self::Internal { digest, .. } => digest,
+            Self::Leaf { digest, .. } => digest,
+        }
+    }
+
+    fn internal(children: Vec<Self>) -> Self {
+        Self::Internal {
+            digest: digest::internal_digest::<D>(children.iter().map(Self::digest)),
+            children: children.into_boxed_slice(),
+        }
+    }
+
+    fn leaf(chunks: Vec<ResumableChunk<'a, Hash>>, chunk_digests: Vec<D::Output>) -> Self {
+        Self::Leaf {
+            digest: digest::leaf_digest::<D>(&chunk_digests),
+            chunks: chunks.into_boxed_slice(),
+            chunk_digests: chunk_digests.into_boxed_slice(),
+        }
+    }
+}
+
+pub struct Tree<'a, Hash: Hasher, D: Digest> {
+    pub root: Box<TreeNode<'a, Hash, D>>,
+}
+
+impl<'a, Hash: Hasher, D: Digest> Tree<'a, Hash, D> {
+    /// The digest of the root, which identifies the whole input.
+    pub fn digest(&self) -> &D::Output {
+        self.root.digest()
+    }
+
+    /// The shape of the tree, with its chunks known only by their digests.
+    ///
+    /// This is what is kept to get the input back: a `store::TreeReader`
+    /// reads it from the chunks in a store, and the
+    /// [`manifest`](crate::manifest) module encodes it.
+    pub fn outline(&self) -> Outline<D> {
+        Outline {
+            root: outline_node(&self.root),
+        }
+    }
+}
+
+fn outline_node<Hash: Hasher, D: Digest>(node: &TreeNode<'_, Hash, D>) -> OutlineNode<D> {
+    match node {
+        TreeNode::Internal { digest, children } => {
+            let children: Box<[_]> = children.iter().map(outline_node).collect();
+
+            OutlineNode::Internal {
+                digest: digest.clone(),
+                len: children.iter().map(OutlineNode::len).sum(),
+                children,
+            }
+        }
+        TreeNode::Leaf {
+            digest,
+            chunks,
+            chunk_digests,
+        } => {
+            let chunks: Box<[_]> = chunks
+                .iter()
+                .zip(chunk_digests.iter())
+                .map(|(chunk, digest)| ChunkRef {
+                    digest: digest.clone(),
+                    len: chunk.len() as u64,
+                    level: chunk.level,
+                })
+                .collect();
+
+            OutlineNode::Leaf {
+                digest: digest.clone(),
+                len: chunks.iter().map(|chunk| chunk.len).sum(),
+                chunks,
+            }
+        }
+    }
+}
+
+/// A chunk known by its digest, as it appears in an [`Outline`].
+pub struct ChunkRef<D: Digest> {
+    pub digest: D::Output,
+    pub len: u64,
+    /// The level of the checksum that ended the chunk, as in
+    /// [`ResumableChunk::level`].
+    pub level: Option<u32>,
+}
+
+impl<D: Digest> Clone for ChunkRef<D> {
+    fn clone(&self) -> Self {
+        Self {
+            digest: self.digest.clone(),
+            len: self.len,
+            level: self.level,
+        }
+    }
+}
+
+/// A node of an [`Outline`], which records the total length of the chunks
+/// below it so that an offset into the input can be found without them.
+pub enum OutlineNode<D: Digest> {
+    Internal {
+        digest: D::Output,
+        len: u64,
+        children: Box<[Self]>,
+    },
+    Leaf {
+        digest: D::Output,
+        len: u64,
+        chunks: Box<[ChunkRef<D>]>,
+    },
+}
+
+impl<D: Digest> OutlineNode<D> {
+    pub fn digest(&self) -> &D::Output {
+        match self {
+            Self::Internal { digest, .. } => digest,
+            Self::Leaf { digest, .. } => digest,
+        }
+    }
+
+    pub fn len(&self) -> u64 {
+        match self {
+            Self::Internal { len, .. } => *len,
+            Self::Leaf { len, .. } => *len,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Whether the digest and length of this node and every node below it
+    /// agree with their children. Lengths whose sum overflows never agree.
+    pub fn verify(&self) -> bool {
+        fn sum(mut lens: impl Iterator<Item = u64>) -> Option<u64> {
+            lens.try_fold(0, u64::checked_add)
+        }
+
+        match self {
+            Self::Internal {
+                digest,
+                len,
+                children,
+            } => {
+                children.iter().all(Self::verify)
+                    && Some(*len) == sum(children.iter().map(Self::len))
+                    && *digest == digest::internal_digest::<D>(children.iter().map(Self::digest))
+            }
+            Self::Leaf {
+                digest,
+                len,
+                chunks,
+            } => {
+                Some(*len) == sum(chunks.iter().map(|chunk| chunk.len))
+                    && *digest == digest::leaf_digest::<D>(chunks.iter().map(|chunk| &chunk.digest))
+            }
+        }
+    }
+}
+
+/// The shape of a [`Tree`] and the digests and lengths of its chunks, which
+/// is enough to put the input back together from a store of chunks.
+pub struct Outline<D: Digest> {
+    pub root: OutlineNode<D>,
+}
+
+impl<D: Digest> Outline<D> {
+    /// An outline with a single leaf holding the given chunks, for inputs
+    /// that are known by a plain list of chunks.
+    pub fn from_chunks(chunks: Vec<ChunkRef<D>>) -> Self {
+        Self {
+            root: OutlineNode::Leaf {
+                digest: digest::leaf_digest::<D>(chunks.iter().map(|chunk| &chunk.digest)),
+                len: chunks.iter().map(|chunk| chunk.len).sum(),
+                chunks: chunks.into_boxed_slice(),
+            },
+        }
+    }
+
+    /// The digest of the root, which is that of the [`Tree`] it outlines.
+    pub fn digest(&self) -> &D::Output {
+        self.root.digest()
+    }
+
+    /// The length of the input.
+    pub fn len(&self) -> u64 {
+        self.root.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// Groups chunks into a [`Tree`] according to their levels, computing the
+/// digest of every node along the way.
+///
+/// Chunks are collected into leaves. A chunk whose level exceeds the
+/// threshold by `L` closes the `L` lowest open nodes after it is added: the
+/// leaf, its parent, and so on, so that the next chunk starts a new node at
+/// each of those heights. Capped chunks and the final chunk count as having
+/// the threshold level, closing nothing, and [`finish`](Self::finish) closes
+/// whatever is still open.
+///
+/// ```
+/// # #[cfg(feature = "blake3")]
+/// # fn main() {
+/// use hashsplit::algorithms::Cp32;
+/// use hashsplit::chunk::TreeBuilder;
+/// use hashsplit::digest::Blake3;
+/// use hashsplit::Config;
+///
+/// let cfg: Config<Cp32, 8, 64, 1024> = Default::default();
+/// let data = [0; 10_000];
+///
+/// let mut builder = TreeBuilder::<_, Blake3>::new(8);
+/// builder.extend(cfg.spans(&data));
+/// let tree = builder.finish().unwrap();
+///
+/// let digest: &[u8; 32] = tree.digest();
+/// # }
+/// # #[cfg(not(feature = "blake3"))]
+/// # fn main() {}
+/// ```
+pub struct TreeBuilder<'a, Hash: Hasher, D: Digest> {
+    threshold: u32,
+    leaf: Vec<ResumableChunk<'a, Hash>>,
+    leaf_digests: Vec<D::Output>,
+    // `open[h]` holds the children of the open node at height `h + 1`.
+    open: Vec<Vec<TreeNode<'a, Hash, D>>>,
 }
 
-pub struct Tree<'a, Hash: Hasher> {
-    pub root: Box<TreeNode<'a, Hash>>,
+impl<'a, Hash: Hasher, D: Digest> TreeBuilder<'a, Hash, D> {
+    pub fn new(threshold: u32) -> Self {
+        Self {
+            threshold,
+            leaf: Vec::new(),
+            leaf_digests: Vec::new(),
+            open: Vec::new(),
+        }
+    }
+
+    pub fn push(&mut self, chunk: ResumableChunk<'a, Hash>) {
+        let height = chunk
+            .level
+            .map_or(0, |lev| lev.saturating_sub(self.threshold)) as usize;
+
+        self.leaf_digests.push(digest::chunk_digest::<D>(&chunk));
+        self.leaf.push(chunk);
+
+        for h in 0..height {
+            self.close(h);
+        }
+    }
+
+    /// Closes the open node at height `h`, adding it to its parent.
+    fn close(&mut self, h: usize) {
+        let node = if h == 0 {
+            TreeNode::leaf(
+                core::mem::take(&mut self.leaf),
+                core::mem::take(&mut self.leaf_digests),
+            )
+        } else {
+            TreeNode::internal(core::mem::take(&mut self.open[h - 1]))
+        };
+
+        if self.open.len() == h {
+            self.open.push(Vec::new());
+        }
+        self.open[h].push(node);
+    }
+
+    /// Closes all open nodes, returning the tree or `None` if no chunks were
+    /// pushed.
+    ///
+    /// A root with a single child is replaced by that child.
+    pub fn finish(mut self) -> Option<Tree<'a, Hash, D>> {
+        if !self.leaf.is_empty() {
+            self.close(0);
+        }
+
+        let mut h = 0;
+        while h < self.open.len() {
+            let is_top = self.open[h + 1..].iter().all(Vec::is_empty);
+            if is_top && self.open[h].len() == 1 {
+                break;
+            }
+            if !self.open[h].is_empty() {
+                self.close(h + 1);
+            }
+            h += 1;
+        }
+
+        let mut root = self.open.get_mut(h)?.pop()?;
+        while let TreeNode::Internal { digest, children } = root {
+            if children.len() != 1 {
+                root = TreeNode::Internal { digest, children };
+                break;
+            }
+            root = children.into_vec().pop()?;
+        }
+
+        Some(Tree {
+            root: Box::new(root),
+        })
+    }
+}
+
+impl<'a, Hash: Hasher, D: Digest> Extend<ResumableChunk<'a, Hash>> for TreeBuilder<'a, Hash, D> {
+    fn extend<I: IntoIterator<Item = ResumableChunk<'a, Hash>>>(&mut self, iter: I) {
+        for chunk in iter {
+            self.push(chunk);
+        }
+    }
+}
+
+/// A node completed by a [`StreamingTreeBuilder`].
+pub struct Node<D: Digest> {
+    /// The height of the node, where leaves have height 0.
+    pub height: u32,
+    pub digest: D::Output,
+    /// The total length of the chunks below the node.
+    pub len: u64,
+    /// The number of chunks of a leaf, or of children of an internal node.
+    pub children: usize,
+}
+
+impl<D: Digest> Clone for Node<D> {
+    fn clone(&self) -> Self {
+        Self {
+            height: self.height,
+            digest: self.digest.clone(),
+            len: self.len,
+            children: self.children,
+        }
+    }
+}
+
+struct OpenNode<D: Digest> {
+    hasher: D,
+    len: u64,
+    children: usize,
+}
+
+/// Builds the same tree as [`TreeBuilder`] without keeping it in memory.
+///
+/// Only one open node per height is kept, each holding a running digest of
+/// its children. Every node of the tree is passed to a callback once it is
+/// closed, after all of its children: the children of a node at height
+/// `h + 1` are the nodes at height `h` emitted since the previous node at
+/// height `h + 1`, and the chunks of a leaf are those pushed since the
+/// previous leaf.
+///
+/// A node that holds every chunk pushed so far may turn out to be a parent
+/// with a single child above the root, which the root replaces, so such
+/// nodes are held back until the next chunk is pushed or until
+/// [`finish`](Self::finish). The nodes replaced by the root are never
+/// emitted.
+///
+/// ```
+/// # #[cfg(feature = "blake3")]
+/// # fn main() {
+/// use hashsplit::algorithms::Cp32;
+/// use hashsplit::chunk::StreamingTreeBuilder;
+/// use hashsplit::digest::Blake3;
+/// use hashsplit::Config;
+///
+/// let cfg: Config<Cp32, 8, 64, 1024> = Default::default();
+/// let data = [0; 10_000];
+///
+/// let mut nodes = Vec::new();
+/// let mut builder = StreamingTreeBuilder::<Blake3, _>::new(8, |node| nodes.push(node));
+/// for chunk in cfg.spans(&data) {
+///     builder.push(&chunk);
+/// }
+/// let root = builder.finish().unwrap();
+///
+/// assert_eq!(root.len, data.len() as u64);
+/// # }
+/// # #[cfg(not(feature = "blake3"))]
+/// # fn main() {}
+/// ```
+pub struct StreamingTreeBuilder<D: Digest, F> {
+    threshold: u32,
+    // `open[h]` is the open node at height `h`.
+    open: Vec<OpenNode<D>>,
+    // Closed nodes that hold every chunk so far, from the lowest up.
+    held: Vec<Node<D>>,
+    emit: F,
+}
+
+impl<D: Digest, F: FnMut(Node<D>)> StreamingTreeBuilder<D, F> {
+    pub fn new(threshold: u32, emit: F) -> Self {
+        Self {
+            threshold,
+            open: Vec::new(),
+            held: Vec::new(),
+            emit,
+        }
+    }
+
+    pub fn push<Hash: Hasher>(&mut self, chunk: &ResumableChunk<'_, Hash>) {
+        let height = chunk
+            .level
+            .map_or(0, |lev| lev.saturating_sub(self.threshold)) as usize;
+
+        // The chunk is outside the held nodes, so they are not the root.
+        for node in core::mem::take(&mut self.held) {
+            (self.emit)(node);
+        }
+
+        let leaf = self.open_at(0);
+        leaf.hasher
+            .update(digest::chunk_digest::<D>(chunk).as_ref());
+        leaf.len += chunk.len() as u64;
+        leaf.children += 1;
+
+        for h in 0..height {
+            self.close(h);
+        }
+    }
+
+    fn open_at(&mut self, h: usize) -> &mut OpenNode<D> {
+        while self.open.len() <= h {
+            let hasher = if self.open.is_empty() {
+                digest::start_leaf::<D>()
+            } else {
+                digest::start_internal::<D>()
+            };
+            self.open.push(OpenNode {
+                hasher,
+                len: 0,
+                children: 0,
+            });
+        }
+
+        &mut self.open[h]
+    }
+
+    /// Closes the open node at height `h`, emitting or holding it and adding
+    /// it to its parent.
+    fn close(&mut self, h: usize) {
+        let fresh = if h == 0 {
+            digest::start_leaf::<D>()
+        } else {
+            digest::start_internal::<D>()
+        };
+        let open = core::mem::replace(
+            &mut self.open[h],
+            OpenNode {
+                hasher: fresh,
+                len: 0,
+                children: 0,
+            },
+        );
+        let whole = self.open[h + 1..].iter().all(|open| open.children == 0);
+
+        let node: Node<D> = Node {
+            height: h as u32,
+            digest: open.hasher.finalize(),
+            len: open.len,
+            children: open.children,
+        };
+
+        let parent = self.open_at(h + 1);
+        parent.hasher.update(node.digest.as_ref());
+        parent.len += node.len;
+        parent.children += 1;
+
+        if whole {
+            self.held.push(node);
+        } else {
+            (self.emit)(node);
+        }
+    }
+
+    /// Closes all open nodes, returning the root, which has already been
+    /// emitted, or `None` if no chunks were pushed.
+    ///
+    /// As with [`TreeBuilder`], a root with a single child is replaced by
+    /// that child.
+    pub fn finish(mut self) -> Option<Node<D>> {
+        if self.open.first()?.children > 0 {
+            self.close(0);
+        }
+
+        let mut h = 1;
+        while h < self.open.len() {
+            let is_top = self.open[h + 1..].iter().all(|open| open.children == 0);
+            if is_top && self.open[h].children == 1 {
+                break;
+            }
+            if self.open[h].children > 0 {
+                self.close(h);
+            }
+            h += 1;
+        }
+
+        // Every held node above the lowest has that one as its only child.
+        let root = self.held.drain(..).next()?;
+        (self.emit)(root.clone());
+
+        Some(root)
+    }
+}
+
+impl<'a, Hash: Hasher, D: Digest, F: FnMut(Node<D>)> Extend<ResumableChunk<'a, Hash>>
+    for StreamingTreeBuilder<D, F>
+{
+    fn extend<I: IntoIterator<Item = ResumableChunk<'a, Hash>>>(&mut self, iter: I) {
+        for chunk in iter {
+            self.push(&chunk);
+        }
+    }
 }
@@ -1,11 +1,141 @@
+use crate::algorithms::{Any, KeyedCp32, Rrs1};
+#[cfg(feature = "rayon")]
+use crate::chunk::ResumableChunk;
+#[cfg(feature = "std")]
+use crate::io::ReadChunks;
+use crate::iter::{Delimited, Distances, FastCdc};
 #[cfg(feature = "alloc")]
-use crate::iter::Spans;
-use crate::iter::{Delimited, Distances};
+use crate::iter::{Extend, IndexedSpans, Spans, ThinnedSpans};
+#[cfg(feature = "rayon")]
+use crate::par::{par_spans, DEFAULT_SEGMENT_SIZE};
+#[cfg(feature = "alloc")]
+use crate::rechunk::{rechunk, Rechunk};
+#[cfg(feature = "futures")]
+use crate::stream::{AsyncReadChunks, StreamChunks};
+#[cfg(feature = "alloc")]
+use crate::thin::Thinned;
 #[allow(unused)]
 use crate::util::*;
-use crate::{Hasher, Named};
+#[cfg(feature = "alloc")]
+use crate::Leveled;
+use crate::{Hasher, Memoryless, Named, Ring, Windowless, WINDOW_SIZE};
 
+#[cfg(feature = "rayon")]
+use alloc::vec::Vec;
 use core::fmt;
+#[cfg(feature = "alloc")]
+use core::ops::Range;
+use core::str::FromStr;
+
+/// The parameters that decide where a chunk ends.
+pub trait Limits {
+    /// The level a checksum must reach to end a chunk of length `length`.
+    fn threshold(&self, length: usize) -> u32;
+
+    /// The length a chunk must reach before its checksum can end it.
+    fn min_size(&self) -> usize;
+
+    /// The length at which a chunk ends whatever its checksum, or 0 if there
+    /// is no such length. Unless it is 0, it must be at least
+    /// [`min_size`](Self::min_size).
+    fn max_size(&self) -> usize;
+}
+
+/// Limits fixed at compile time.
+#[derive(Clone, Copy, Default)]
+pub struct Fixed<const THRESHOLD: u32, const MIN_SIZE: usize, const MAX_SIZE: usize>;
+
+impl<const THRESHOLD: u32, const MIN_SIZE: usize, const MAX_SIZE: usize> Limits
+    for Fixed<THRESHOLD, MIN_SIZE, MAX_SIZE>
+{
+    fn threshold(&self, _length: usize) -> u32 {
+        THRESHOLD
+    }
+
+    fn min_size(&self) -> usize {
+        MIN_SIZE
+    }
+
+    fn max_size(&self) -> usize {
+        MAX_SIZE
+    }
+}
+
+/// How many levels stricter (below `NORMAL_SIZE`) or looser (above it) than
+/// `THRESHOLD` the threshold of [`Normalized`] limits is.
+pub const NORMALIZATION: u32 = 2;
+
+/// Limits in the style of FastCDC, fixed at compile time.
+///
+/// A chunk shorter than `NORMAL_SIZE` only ends at a checksum of level at
+/// least `THRESHOLD + NORMALIZATION`, and a longer one at a checksum of level
+/// at least `THRESHOLD - NORMALIZATION`. This pulls chunk lengths towards
+/// `NORMAL_SIZE`.
+#[derive(Clone, Copy, Default)]
+pub struct Normalized<
+    const THRESHOLD: u32,
+    const MIN_SIZE: usize,
+    const NORMAL_SIZE: usize,
+    const MAX_SIZE: usize,
+>;
+
+impl<
+        const THRESHOLD: u32,
+        const MIN_SIZE: usize,
+        const NORMAL_SIZE: usize,
+        const MAX_SIZE: usize,
+    > Limits for Normalized<THRESHOLD, MIN_SIZE, NORMAL_SIZE, MAX_SIZE>
+{
+    fn threshold(&self, length: usize) -> u32 {
+        if length < NORMAL_SIZE {
+            THRESHOLD.saturating_add(NORMALIZATION)
+        } else {
+            THRESHOLD.saturating_sub(NORMALIZATION)
+        }
+    }
+
+    fn min_size(&self) -> usize {
+        MIN_SIZE
+    }
+
+    fn max_size(&self) -> usize {
+        MAX_SIZE
+    }
+}
+
+/// Limits chosen at runtime.
+///
+/// Unless `normal_size` is 0, the threshold is normalized as for
+/// [`Normalized`] limits.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DynLimits {
+    pub threshold: u32,
+    pub min_size: usize,
+    /// The length that chunk lengths are pulled towards, or 0 for none.
+    pub normal_size: usize,
+    /// The maximum chunk size, or 0 for none.
+    pub max_size: usize,
+}
+
+impl Limits for DynLimits {
+    fn threshold(&self, length: usize) -> u32 {
+        if self.normal_size == 0 {
+            self.threshold
+        } else if length < self.normal_size {
+            self.threshold.saturating_add(NORMALIZATION)
+        } else {
+            self.threshold.saturating_sub(NORMALIZATION)
+        }
+    }
+
+    fn min_size(&self) -> usize {
+        self.min_size
+    }
+
+    fn max_size(&self) -> usize {
+        self.max_size
+    }
+}
 
 #[derive(Clone, Copy, Default)]
 pub struct Config<Hash, const THRESHOLD: u32, const MIN_SIZE: usize, const MAX_SIZE: usize> {
@@ -22,21 +152,414 @@ impl<Hash: Hasher, const THRESHOLD: u32, const MIN_SIZE: usize, const MAX_SIZE:
     pub fn delimited<Source: Iterator<Item = u8>>(
         self,
         source: Source,
-    ) -> Delimited<Hash, Source, THRESHOLD, MIN_SIZE, MAX_SIZE> {
-        Delimited::start(self.hasher, source)
+    ) -> Delimited<Hash, Source, Fixed<THRESHOLD, MIN_SIZE, MAX_SIZE>> {
+        Delimited::start(self.hasher, source, Fixed)
+    }
+
+    pub fn distances<Source: Iterator<Item = u8>>(
+        self,
+        source: Source,
+    ) -> Distances<Hash, Source, Fixed<THRESHOLD, MIN_SIZE, MAX_SIZE>> {
+        Distances::start(self.hasher, source, Fixed)
+    }
+
+    /// See [`Delimited::resume`].
+    pub fn resume_delimited<Source: Iterator<Item = u8>>(
+        self,
+        source: Source,
+        state: Hash::State,
+        tail: &[u8],
+    ) -> Delimited<Hash, Source, Fixed<THRESHOLD, MIN_SIZE, MAX_SIZE>> {
+        Delimited::resume(self.hasher, source, Fixed, state, tail)
+    }
+
+    /// See [`Distances::resume`].
+    pub fn resume_distances<Source: Iterator<Item = u8>>(
+        self,
+        source: Source,
+        state: Hash::State,
+        tail: &[u8],
+    ) -> Distances<Hash, Source, Fixed<THRESHOLD, MIN_SIZE, MAX_SIZE>> {
+        Distances::resume(self.hasher, source, Fixed, state, tail)
+    }
+
+    /// See [`FastCdc`].
+    ///
+    /// The identifier of `self` leaves out `NORMAL_SIZE`, so it does not
+    /// describe these chunks. That of `DynConfig::from(self)`,
+    /// [`normalized`](DynConfig::normalized) to `NORMAL_SIZE`, finds the same
+    /// chunks and records it.
+    pub fn fast_cdc<const NORMAL_SIZE: usize, Source: Iterator<Item = u8>>(
+        self,
+        source: Source,
+    ) -> FastCdc<Hash, Source, Normalized<THRESHOLD, MIN_SIZE, NORMAL_SIZE, MAX_SIZE>>
+    where
+        Hash: Windowless + Memoryless,
+    {
+        FastCdc::start(self.hasher, source, Normalized)
+    }
+
+    #[cfg(feature = "alloc")]
+    #[doc(cfg(feature = "alloc"))]
+    pub fn spans(self, data: &[u8]) -> Spans<'_, Hash, Fixed<THRESHOLD, MIN_SIZE, MAX_SIZE>> {
+        Spans::start(self.hasher, data, Fixed)
+    }
+
+    #[cfg(feature = "alloc")]
+    #[doc(cfg(feature = "alloc"))]
+    pub fn indexed_spans(
+        self,
+        data: &[u8],
+    ) -> IndexedSpans<'_, Hash, Fixed<THRESHOLD, MIN_SIZE, MAX_SIZE>>
+    where
+        Hash: Memoryless,
+    {
+        IndexedSpans::start(self.hasher, data, Fixed)
+    }
+
+    #[cfg(feature = "alloc")]
+    #[doc(cfg(feature = "alloc"))]
+    pub fn thinned_spans<const N: usize>(
+        self,
+        data: &[u8],
+    ) -> ThinnedSpans<'_, Hash, Fixed<THRESHOLD, MIN_SIZE, MAX_SIZE>, N>
+    where
+        Hash: Thinned<[u8; N]> + Memoryless,
+    {
+        ThinnedSpans::start(self.hasher, data, Fixed)
+    }
+
+    /// See [`rechunk`].
+    #[cfg(feature = "alloc")]
+    #[doc(cfg(feature = "alloc"))]
+    pub fn rechunk(self, old: &[Extend<Hash>], edited: Range<usize>, data: &[u8]) -> Rechunk<Hash>
+    where
+        Hash: Memoryless,
+        Hash::Checksum: Leveled,
+        Hash::State: Clone,
+    {
+        rechunk(
+            self.hasher,
+            Fixed::<THRESHOLD, MIN_SIZE, MAX_SIZE>,
+            old,
+            edited,
+            data,
+        )
+    }
+
+    #[cfg(feature = "rayon")]
+    #[doc(cfg(feature = "rayon"))]
+    pub fn par_spans(self, data: &[u8]) -> Vec<ResumableChunk<'_, Hash>>
+    where
+        Hash: Memoryless + Clone + Send + Sync,
+        Hash::Checksum: Leveled,
+        Hash::State: Clone + Send,
+    {
+        par_spans(
+            self.hasher,
+            data,
+            Fixed::<THRESHOLD, MIN_SIZE, MAX_SIZE>,
+            DEFAULT_SEGMENT_SIZE,
+        )
+    }
+
+    #[cfg(feature = "std")]
+    #[doc(cfg(feature = "std"))]
+    pub fn read_chunks<Reader: std::io::Read>(
+        self,
+        reader: Reader,
+    ) -> ReadChunks<Hash, Reader, Fixed<THRESHOLD, MIN_SIZE, MAX_SIZE>>
+    where
+        Hash::Checksum: Leveled,
+        Hash::State: Clone,
+    {
+        ReadChunks::start(self.hasher, reader, Fixed)
+    }
+
+    #[cfg(feature = "futures")]
+    #[doc(cfg(feature = "futures"))]
+    pub fn async_read_chunks<Reader: futures_io::AsyncRead + Unpin>(
+        self,
+        reader: Reader,
+    ) -> AsyncReadChunks<Hash, Reader, Fixed<THRESHOLD, MIN_SIZE, MAX_SIZE>>
+    where
+        Hash::Checksum: Leveled,
+        Hash::State: Clone,
+    {
+        AsyncReadChunks::start(self.hasher, reader, Fixed)
+    }
+
+    #[cfg(feature = "futures")]
+    #[doc(cfg(feature = "futures"))]
+    pub fn stream_chunks<Source, Buffer>(
+        self,
+        source: Source,
+    ) -> StreamChunks<Hash, Source, Buffer, Fixed<THRESHOLD, MIN_SIZE, MAX_SIZE>>
+    where
+        Hash::Checksum: Leveled,
+        Hash::State: Clone,
+    {
+        StreamChunks::start(self.hasher, source, Fixed)
+    }
+}
+
+/// A configuration whose parameters are chosen at runtime.
+///
+/// This yields the same chunks as the corresponding [`Config`], at the cost of
+/// reading the parameters from memory.
+///
+/// ```
+/// use hashsplit::algorithms::Cp32;
+/// use hashsplit::config::DynConfig;
+///
+/// let cfg = DynConfig::new(Cp32, 8, 64, 1024);
+///
+/// assert_eq!(cfg.to_string(), "HashSplit_8_cp32_W64_64_1Ki");
+/// ```
+#[derive(Clone, Copy)]
+pub struct DynConfig<Hash> {
+    pub hasher: Hash,
+    pub threshold: u32,
+    pub min_size: usize,
+    /// The length that chunk lengths are pulled towards, or 0 for none. See
+    /// [`normalized`](Self::normalized).
+    pub normal_size: usize,
+    /// The maximum chunk size, or 0 for none.
+    pub max_size: usize,
+}
+
+impl<Hash: Hasher> DynConfig<Hash> {
+    /// A `max_size` of 0 means that chunks have no maximum size.
+    ///
+    /// # Panics
+    ///
+    /// If `max_size` is neither 0 nor at least `min_size`.
+    pub fn new(hasher: Hash, threshold: u32, min_size: usize, max_size: usize) -> Self {
+        assert!(
+            max_size == 0 || min_size <= max_size,
+            "the maximum chunk size must be 0 or at least the minimum size"
+        );
+
+        Self {
+            hasher,
+            threshold,
+            min_size,
+            normal_size: 0,
+            max_size,
+        }
+    }
+
+    /// Normalizes the threshold in the style of FastCDC, as for
+    /// [`Normalized`] limits: chunks shorter than `normal_size` need a checksum
+    /// [`NORMALIZATION`] levels above the threshold to end, and longer ones a
+    /// checksum that many levels below. A `normal_size` of 0 turns this off.
+    ///
+    /// Every chunker honours it, and the identifier records it.
+    ///
+    /// ```
+    /// use hashsplit::algorithms::Gear64;
+    /// use hashsplit::config::DynConfig;
+    ///
+    /// let cfg = DynConfig::new(Gear64, 10, 256, 4096).normalized(1024);
+    /// assert_eq!(cfg.to_string(), "HashSplit_10_gear64_W64_256_4Ki_N1Ki_L2");
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If `normal_size` is neither 0 nor between the minimum and maximum
+    /// sizes.
+    pub fn normalized(self, normal_size: usize) -> Self {
+        assert!(
+            normal_size == 0
+                || (self.min_size <= normal_size
+                    && (self.max_size == 0 || normal_size <= self.max_size)),
+            "the normal chunk size must be 0 or between the minimum and maximum sizes"
+        );
+
+        Self {
+            normal_size,
+            ..self
+        }
+    }
+
+    pub fn limits(&self) -> DynLimits {
+        DynLimits {
+            threshold: self.threshold,
+            min_size: self.min_size,
+            normal_size: self.normal_size,
+            max_size: self.max_size,
+        }
+    }
+
+    pub fn delimited<Source: Iterator<Item = u8>>(
+        self,
+        source: Source,
+    ) -> Delimited<Hash, Source, DynLimits> {
+        let limits = self.limits();
+
+        Delimited::start(self.hasher, source, limits)
     }
 
     pub fn distances<Source: Iterator<Item = u8>>(
         self,
         source: Source,
-    ) -> Distances<Hash, Source, THRESHOLD, MIN_SIZE, MAX_SIZE> {
-        Distances::start(self.hasher, source)
+    ) -> Distances<Hash, Source, DynLimits> {
+        let limits = self.limits();
+
+        Distances::start(self.hasher, source, limits)
+    }
+
+    /// See [`Delimited::resume`].
+    pub fn resume_delimited<Source: Iterator<Item = u8>>(
+        self,
+        source: Source,
+        state: Hash::State,
+        tail: &[u8],
+    ) -> Delimited<Hash, Source, DynLimits> {
+        let limits = self.limits();
+
+        Delimited::resume(self.hasher, source, limits, state, tail)
+    }
+
+    /// See [`Distances::resume`].
+    pub fn resume_distances<Source: Iterator<Item = u8>>(
+        self,
+        source: Source,
+        state: Hash::State,
+        tail: &[u8],
+    ) -> Distances<Hash, Source, DynLimits> {
+        let limits = self.limits();
+
+        Distances::resume(self.hasher, source, limits, state, tail)
+    }
+
+    /// See [`FastCdc`].
+    pub fn fast_cdc<Source: Iterator<Item = u8>>(
+        self,
+        source: Source,
+    ) -> FastCdc<Hash, Source, DynLimits>
+    where
+        Hash: Windowless + Memoryless,
+    {
+        let limits = self.limits();
+
+        FastCdc::start(self.hasher, source, limits)
     }
 
     #[cfg(feature = "alloc")]
     #[doc(cfg(feature = "alloc"))]
-    pub fn spans<'a>(self, data: &'a [u8]) -> Spans<'a, Hash, THRESHOLD, MIN_SIZE, MAX_SIZE> {
-        Spans::start(self.hasher, data)
+    pub fn spans(self, data: &[u8]) -> Spans<'_, Hash, DynLimits> {
+        let limits = self.limits();
+
+        Spans::start(self.hasher, data, limits)
+    }
+
+    #[cfg(feature = "alloc")]
+    #[doc(cfg(feature = "alloc"))]
+    pub fn indexed_spans(self, data: &[u8]) -> IndexedSpans<'_, Hash, DynLimits>
+    where
+        Hash: Memoryless,
+    {
+        let limits = self.limits();
+
+        IndexedSpans::start(self.hasher, data, limits)
+    }
+
+    #[cfg(feature = "alloc")]
+    #[doc(cfg(feature = "alloc"))]
+    pub fn thinned_spans<const N: usize>(self, data: &[u8]) -> ThinnedSpans<'_, Hash, DynLimits, N>
+    where
+        Hash: Thinned<[u8; N]> + Memoryless,
+    {
+        let limits = self.limits();
+
+        ThinnedSpans::start(self.hasher, data, limits)
+    }
+
+    /// See [`rechunk`].
+    #[cfg(feature = "alloc")]
+    #[doc(cfg(feature = "alloc"))]
+    pub fn rechunk(self, old: &[Extend<Hash>], edited: Range<usize>, data: &[u8]) -> Rechunk<Hash>
+    where
+        Hash: Memoryless,
+        Hash::Checksum: Leveled,
+        Hash::State: Clone,
+    {
+        let limits = self.limits();
+
+        rechunk(self.hasher, limits, old, edited, data)
+    }
+
+    #[cfg(feature = "rayon")]
+    #[doc(cfg(feature = "rayon"))]
+    pub fn par_spans(self, data: &[u8]) -> Vec<ResumableChunk<'_, Hash>>
+    where
+        Hash: Memoryless + Clone + Send + Sync,
+        Hash::Checksum: Leveled,
+        Hash::State: Clone + Send,
+    {
+        let limits = self.limits();
+
+        par_spans(self.hasher, data, limits, DEFAULT_SEGMENT_SIZE)
+    }
+
+    #[cfg(feature = "std")]
+    #[doc(cfg(feature = "std"))]
+    pub fn read_chunks<Reader: std::io::Read>(
+        self,
+        reader: Reader,
+    ) -> ReadChunks<Hash, Reader, DynLimits>
+    where
+        Hash::Checksum: Leveled,
+        Hash::State: Clone,
+    {
+        let limits = self.limits();
+
+        ReadChunks::start(self.hasher, reader, limits)
+    }
+
+    #[cfg(feature = "futures")]
+    #[doc(cfg(feature = "futures"))]
+    pub fn async_read_chunks<Reader: futures_io::AsyncRead + Unpin>(
+        self,
+        reader: Reader,
+    ) -> AsyncReadChunks<Hash, Reader, DynLimits>
+    where
+        Hash::Checksum: Leveled,
+        Hash::State: Clone,
+    {
+        let limits = self.limits();
+
+        AsyncReadChunks::start(self.hasher, reader, limits)
+    }
+
+    #[cfg(feature = "futures")]
+    #[doc(cfg(feature = "futures"))]
+    pub fn stream_chunks<Source, Buffer>(
+        self,
+        source: Source,
+    ) -> StreamChunks<Hash, Source, Buffer, DynLimits>
+    where
+        Hash::Checksum: Leveled,
+        Hash::State: Clone,
+    {
+        let limits = self.limits();
+
+        StreamChunks::start(self.hasher, source, limits)
+    }
+}
+
+impl<Hash, const THRESHOLD: u32, const MIN_SIZE: usize, const MAX_SIZE: usize>
+    From<Config<Hash, THRESHOLD, MIN_SIZE, MAX_SIZE>> for DynConfig<Hash>
+{
+    fn from(cfg: Config<Hash, THRESHOLD, MIN_SIZE, MAX_SIZE>) -> Self {
+        Self {
+            hasher: cfg.hasher,
+            threshold: THRESHOLD,
+            min_size: MIN_SIZE,
+            normal_size: 0,
+            max_size: MAX_SIZE,
+        }
     }
 }
 
@@ -46,11 +569,13 @@ impl fmt::Display for Size {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
         let z = self.0;
 
-        if z % (1 << 30) == 0 {
+        if z == 0 {
+            write!(f, "0")
+        } else if z.is_multiple_of(1 << 30) {
             write!(f, "{}Gi", z >> 30)
-        } else if z % (1 << 20) == 0 {
+        } else if z.is_multiple_of(1 << 20) {
             write!(f, "{}Mi", z >> 20)
-        } else if z % (1 << 10) == 0 {
+        } else if z.is_multiple_of(1 << 10) {
             write!(f, "{}Ki", z >> 10)
         } else {
             write!(f, "{}", z)
@@ -58,25 +583,311 @@ impl fmt::Display for Size {
     }
 }
 
+impl FromStr for Size {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (digits, shift) = if let Some(digits) = s.strip_suffix("Gi") {
+            (digits, 30)
+        } else if let Some(digits) = s.strip_suffix("Mi") {
+            (digits, 20)
+        } else if let Some(digits) = s.strip_suffix("Ki") {
+            (digits, 10)
+        } else {
+            (s, 0)
+        };
+
+        parse_decimal::<usize>(digits)
+            .and_then(|z| z.checked_mul(1 << shift))
+            .map(Size)
+            .ok_or(ParseError::BadSize)
+    }
+}
+
+fn parse_decimal<T: FromStr>(s: &str) -> Option<T> {
+    // `FromStr` for integers also accepts a leading `+`.
+    if s.bytes().all(|b| b.is_ascii_digit()) {
+        s.parse().ok()
+    } else {
+        None
+    }
+}
+
+/// The ways in which a configuration identifier can fail to parse.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ParseError {
+    /// The identifier does not have the shape
+    /// `HashSplit_<threshold>_<algorithm>_W<window>_<min size>_<max size>`,
+    /// followed by `_N<normal size>_L<normalization>` if the threshold is
+    /// normalized.
+    Malformed,
+    BadThreshold,
+    /// The window size is not a number.
+    BadWindow,
+    /// The window size is not [`WINDOW_SIZE`], the only one that [`Any`]
+    /// supports.
+    UnsupportedWindow,
+    /// A chunk size is invalid, the maximum size is less than the minimum, or
+    /// the normal size is not between them.
+    BadSize,
+    /// The normalization is not a number.
+    BadNormalization,
+    /// The normalization is not [`NORMALIZATION`], the only one that
+    /// [`DynConfig`] supports.
+    UnsupportedNormalization,
+    /// The algorithm name is not the [`Named::NAME`] of any hasher that
+    /// [`Any`] can hold.
+    UnknownAlgorithm,
+    /// The algorithm is [`KeyedCp32`], which needs a secret key that
+    /// identifiers leave out.
+    KeyRequired,
+    /// The identifier was written before window sizes were recorded, and
+    /// names an algorithm whose boundaries have changed since. This is the
+    /// case for [`Rrs1`].
+    Outdated,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        f.write_str(match self {
+            Self::Malformed => "malformed configuration identifier",
+            Self::BadThreshold => "invalid threshold in configuration identifier",
+            Self::BadWindow => "invalid window size in configuration identifier",
+            Self::UnsupportedWindow => "unsupported window size in configuration identifier",
+            Self::BadSize => "invalid chunk size in configuration identifier",
+            Self::BadNormalization => "invalid normalization in configuration identifier",
+            Self::UnsupportedNormalization => {
+                "unsupported normalization in configuration identifier"
+            }
+            Self::UnknownAlgorithm => "unknown algorithm in configuration identifier",
+            Self::KeyRequired => "configuration identifier names a keyed algorithm",
+            Self::Outdated => "configuration identifier names an algorithm that has changed",
+        })
+    }
+}
+
+#[cfg(feature = "std")]
+#[doc(cfg(feature = "std"))]
+impl std::error::Error for ParseError {}
+
+/// The fields of a configuration identifier, whatever its algorithm and
+/// window size.
+///
+/// Parsing a [`DynConfig<Any>`] only succeeds for the hashers that [`Any`]
+/// can hold; this is for the configurations of other hashers, whose fields
+/// can be checked against what the caller expects.
+///
+/// Identifiers written before window sizes were recorded leave out the `W`
+/// field, and are read as having a window of [`WINDOW_SIZE`] bytes, which
+/// was the only one. They are always written with the field. Those that name
+/// RRS1 are rejected with [`ParseError::Outdated`], since they stand for a
+/// different modulus and initial state than [`Rrs1`] has now.
+///
+/// ```
+/// use hashsplit::config::{Identifier, ParseError};
+///
+/// let id = Identifier::parse("HashSplit_10_buzhash32_W48_64_4Ki").unwrap();
+///
+/// assert_eq!((id.threshold, id.name, id.window), (10, "buzhash32", 48));
+/// assert_eq!((id.min_size, id.max_size), (64, 4096));
+/// assert_eq!("HashSplit_10_buzhash32_W48_64_4Ki", id.to_string());
+///
+/// let legacy = Identifier::parse("HashSplit_13_cp32_64Ki_2Mi").unwrap();
+/// assert_eq!("HashSplit_13_cp32_W64_64Ki_2Mi", legacy.to_string());
+///
+/// let normalized = Identifier::parse("HashSplit_10_gear64_W64_256_4Ki_N1Ki_L2").unwrap();
+/// assert_eq!((normalized.normal_size, normalized.normalization), (1024, 2));
+///
+/// assert_eq!(Identifier::parse("HashSplit_10_buzhash32").err(), Some(ParseError::Malformed));
+/// assert_eq!(Identifier::parse("HashSplit_13_RRS1_64Ki_2Mi").err(), Some(ParseError::Outdated));
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Identifier<'a> {
+    pub threshold: u32,
+    /// The [`Named::NAME`] of the hasher.
+    pub name: &'a str,
+    pub window: usize,
+    pub min_size: usize,
+    /// The maximum chunk size, or 0 for none.
+    pub max_size: usize,
+    /// The length that chunk lengths are pulled towards, or 0 for none.
+    pub normal_size: usize,
+    /// How many levels the threshold is normalized by, or 0 if there is no
+    /// normal size.
+    pub normalization: u32,
+}
+
+impl<'a> Identifier<'a> {
+    pub fn parse(s: &'a str) -> Result<Self, ParseError> {
+        let mut fields = s.split('_');
+        let mut field = || fields.next().ok_or(ParseError::Malformed);
+
+        if field()? != "HashSplit" {
+            return Err(ParseError::Malformed);
+        }
+        let threshold = parse_decimal(field()?).ok_or(ParseError::BadThreshold)?;
+        let name = field()?;
+        let mut next = field()?;
+        let window = match next.strip_prefix('W') {
+            Some(digits) => {
+                next = field()?;
+                parse_decimal(digits).ok_or(ParseError::BadWindow)?
+            }
+            // RRS1 has had its modulus and initial state fixed since.
+            None if name == Rrs1::NAME => return Err(ParseError::Outdated),
+            None => WINDOW_SIZE,
+        };
+        let Size(min_size) = next.parse()?;
+        let Size(max_size) = field()?.parse()?;
+        let (normal, levels) = (fields.next(), fields.next());
+        if fields.next().is_some() {
+            return Err(ParseError::Malformed);
+        }
+        if max_size != 0 && min_size > max_size {
+            return Err(ParseError::BadSize);
+        }
+
+        let (normal_size, normalization) = match (normal, levels) {
+            (None, None) => (0, 0),
+            (Some(normal), Some(levels)) => {
+                let normal = normal.strip_prefix('N').ok_or(ParseError::Malformed)?;
+                let levels = levels.strip_prefix('L').ok_or(ParseError::Malformed)?;
+                let Size(normal_size) = normal.parse()?;
+                if normal_size == 0
+                    || normal_size < min_size
+                    || (max_size != 0 && normal_size > max_size)
+                {
+                    return Err(ParseError::BadSize);
+                }
+
+                (
+                    normal_size,
+                    parse_decimal(levels).ok_or(ParseError::BadNormalization)?,
+                )
+            }
+            _ => return Err(ParseError::Malformed),
+        };
+
+        Ok(Self {
+            threshold,
+            name,
+            window,
+            min_size,
+            max_size,
+            normal_size,
+            normalization,
+        })
+    }
+}
+
+impl fmt::Display for Identifier<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        write!(
+            f,
+            "HashSplit_{}_{}_W{}_{}_{}",
+            self.threshold,
+            self.name,
+            self.window,
+            Size(self.min_size),
+            Size(self.max_size)
+        )?;
+        if self.normal_size != 0 {
+            write!(f, "_N{}_L{}", Size(self.normal_size), self.normalization)?;
+        }
+
+        Ok(())
+    }
+}
+
 /// ```
 /// # use hashsplit::Config;
 /// use hashsplit::algorithms::Rrs1;
 ///
 /// let cfg: Config<Rrs1, 13, 0x01_00_00, 0x20_00_00> = Default::default();
 ///
-/// assert_eq!("HashSplit_13_RRS1_64Ki_2Mi", cfg.to_string());
+/// assert_eq!("HashSplit_13_RRS1_W64_64Ki_2Mi", cfg.to_string());
 /// ```
 impl<Hash: Named, const THRESHOLD: u32, const MIN_SIZE: usize, const MAX_SIZE: usize> fmt::Display
     for Config<Hash, THRESHOLD, MIN_SIZE, MAX_SIZE>
 {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
-        write!(
-            f,
-            "HashSplit_{}_{}_{}_{}",
-            THRESHOLD,
-            Hash::NAME,
-            Size(MIN_SIZE),
-            Size(MAX_SIZE)
-        )
+        Identifier {
+            threshold: THRESHOLD,
+            name: Hash::NAME,
+            window: <Hash::Ring as Ring>::LEN,
+            min_size: MIN_SIZE,
+            max_size: MAX_SIZE,
+            normal_size: 0,
+            normalization: 0,
+        }
+        .fmt(f)
+    }
+}
+
+impl<Hash: Named> fmt::Display for DynConfig<Hash> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        Identifier {
+            threshold: self.threshold,
+            name: Hash::NAME,
+            window: <Hash::Ring as Ring>::LEN,
+            min_size: self.min_size,
+            max_size: self.max_size,
+            normal_size: self.normal_size,
+            normalization: NORMALIZATION,
+        }
+        .fmt(f)
+    }
+}
+
+impl fmt::Display for DynConfig<Any> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        Identifier {
+            threshold: self.threshold,
+            name: self.hasher.name(),
+            window: <<Any as Hasher>::Ring as Ring>::LEN,
+            min_size: self.min_size,
+            max_size: self.max_size,
+            normal_size: self.normal_size,
+            normalization: NORMALIZATION,
+        }
+        .fmt(f)
+    }
+}
+
+/// Parses the identifier written by the `Display` implementations of
+/// [`Config`] and [`DynConfig`].
+///
+/// The hashers that [`Any`] holds all use a window of [`WINDOW_SIZE`] bytes,
+/// so identifiers with any other window size are rejected with
+/// [`ParseError::UnsupportedWindow`], even if they name one of those hashers.
+/// [`Identifier`] reads their fields.
+///
+/// ```
+/// use hashsplit::algorithms::Any;
+/// use hashsplit::config::DynConfig;
+///
+/// let cfg: DynConfig<Any> = "HashSplit_13_RRS1_W64_64Ki_2Mi".parse().unwrap();
+///
+/// assert!(matches!(cfg.hasher, Any::Rrs1(_)));
+/// assert_eq!((cfg.threshold, cfg.min_size, cfg.max_size), (13, 0x01_00_00, 0x20_00_00));
+/// ```
+impl FromStr for DynConfig<Any> {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let id = Identifier::parse(s)?;
+
+        if id.name == KeyedCp32::NAME {
+            return Err(ParseError::KeyRequired);
+        }
+        let hasher = Any::from_name(id.name).ok_or(ParseError::UnknownAlgorithm)?;
+        if id.window != <<Any as Hasher>::Ring as Ring>::LEN {
+            return Err(ParseError::UnsupportedWindow);
+        }
+        if id.normal_size != 0 && id.normalization != NORMALIZATION {
+            return Err(ParseError::UnsupportedNormalization);
+        }
+
+        Ok(Self::new(hasher, id.threshold, id.min_size, id.max_size).normalized(id.normal_size))
     }
 }
@@ -0,0 +1,134 @@
+#[allow(unused)]
+use crate::util::*;
+
+/// A cryptographic hash function, used to identify chunks and tree nodes by
+/// their contents.
+pub trait Digest: Default {
+    type Output: AsRef<[u8]> + Clone + Eq + Ord;
+
+    /// The name of the hash function, which manifests record so that they are
+    /// not decoded with another one whose digests have the same length.
+    const NAME: &'static str;
+
+    fn update(&mut self, data: &[u8]);
+
+    fn finalize(self) -> Self::Output;
+
+    fn digest(data: &[u8]) -> Self::Output {
+        let mut hasher = Self::default();
+        hasher.update(data);
+
+        hasher.finalize()
+    }
+}
+
+const LEAF_PREFIX: u8 = 0;
+
+const INTERNAL_PREFIX: u8 = 1;
+
+const CHUNK_PREFIX: u8 = 2;
+
+/// The digest of a chunk, by which trees and stores know it.
+///
+/// Like those of leaves and internal nodes, it is taken over a prefix byte of
+/// its own, which keeps the three kinds of digest apart: a chunk whose bytes
+/// happen to be those that a node hashes still gets a different digest.
+pub fn chunk_digest<D: Digest>(chunk: &[u8]) -> D::Output {
+    let mut hasher = start::<D>(CHUNK_PREFIX);
+    hasher.update(chunk);
+
+    hasher.finalize()
+}
+
+/// The digest of a leaf, computed from the [`chunk_digest`]s of its chunks.
+pub fn leaf_digest<'b, D: Digest>(
+    chunk_digests: impl IntoIterator<Item = &'b D::Output>,
+) -> D::Output
+where
+    D::Output: 'b,
+{
+    combine::<D>(LEAF_PREFIX, chunk_digests)
+}
+
+/// The digest of an internal node, computed from the digests of its children.
+pub fn internal_digest<'b, D: Digest>(
+    child_digests: impl IntoIterator<Item = &'b D::Output>,
+) -> D::Output
+where
+    D::Output: 'b,
+{
+    combine::<D>(INTERNAL_PREFIX, child_digests)
+}
+
+/// Starts hashing a leaf, to be fed the digests of its chunks.
+#[cfg(feature = "alloc")]
+pub(crate) fn start_leaf<D: Digest>() -> D {
+    start::<D>(LEAF_PREFIX)
+}
+
+/// Starts hashing an internal node, to be fed the digests of its children.
+#[cfg(feature = "alloc")]
+pub(crate) fn start_internal<D: Digest>() -> D {
+    start::<D>(INTERNAL_PREFIX)
+}
+
+fn start<D: Digest>(prefix: u8) -> D {
+    let mut hasher = D::default();
+    hasher.update(&[prefix]);
+
+    hasher
+}
+
+fn combine<'b, D: Digest>(prefix: u8, digests: impl IntoIterator<Item = &'b D::Output>) -> D::Output
+where
+    D::Output: 'b,
+{
+    let mut hasher = start::<D>(prefix);
+    for digest in digests {
+        hasher.update(digest.as_ref());
+    }
+
+    hasher.finalize()
+}
+
+#[cfg(feature = "blake3")]
+#[doc(cfg(feature = "blake3"))]
+#[derive(Default)]
+pub struct Blake3(blake3::Hasher);
+
+#[cfg(feature = "blake3")]
+#[doc(cfg(feature = "blake3"))]
+impl Digest for Blake3 {
+    type Output = [u8; 32];
+
+    const NAME: &'static str = "blake3";
+
+    fn update(&mut self, data: &[u8]) {
+        self.0.update(data);
+    }
+
+    fn finalize(self) -> Self::Output {
+        self.0.finalize().into()
+    }
+}
+
+#[cfg(feature = "sha2")]
+#[doc(cfg(feature = "sha2"))]
+#[derive(Default)]
+pub struct Sha256(sha2::Sha256);
+
+#[cfg(feature = "sha2")]
+#[doc(cfg(feature = "sha2"))]
+impl Digest for Sha256 {
+    type Output = [u8; 32];
+
+    const NAME: &'static str = "sha256";
+
+    fn update(&mut self, data: &[u8]) {
+        sha2::Digest::update(&mut self.0, data);
+    }
+
+    fn finalize(self) -> Self::Output {
+        sha2::Digest::finalize(self.0).into()
+    }
+}
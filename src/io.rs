@@ -0,0 +1,143 @@
+use crate::chunk::ResumableChunk;
+use crate::config::Limits;
+use crate::iter::{reserve, Boundary, Scanner};
+#[allow(unused)]
+use crate::util::*;
+use crate::{Hasher, Leveled};
+
+use std::io::{self, Read};
+use std::vec::Vec;
+
+/// The size of the internal buffer of a [`ReadChunks`] by default.
+pub const DEFAULT_BUFFER_SIZE: usize = 1 << 16;
+
+/// Chunks the bytes of a [`Read`], reading them in large pieces.
+///
+/// ```
+/// use hashsplit::algorithms::Cp32;
+/// use hashsplit::Config;
+///
+/// let cfg: Config<Cp32, 8, 64, 1024> = Default::default();
+/// let input: &[u8] = &[0; 10_000];
+///
+/// let chunks = cfg
+///     .read_chunks(input)
+///     .collect::<std::io::Result<Vec<_>>>()
+///     .unwrap();
+///
+/// assert_eq!(chunks.iter().map(|chunk| chunk.len()).sum::<usize>(), input.len());
+/// ```
+pub struct ReadChunks<Hash: Hasher, Reader, Limit> {
+    assembler: Assembler<Hash, Limit>,
+    buffer: Vec<u8>,
+    begin: usize,
+    end: usize,
+    halt: bool,
+    pub reader: Reader,
+}
+
+impl<Hash: Hasher, Reader: Read, Limit: Limits> ReadChunks<Hash, Reader, Limit>
+where
+    Hash::Checksum: Leveled,
+    Hash::State: Clone,
+{
+    pub fn start(hasher: Hash, reader: Reader, limits: Limit) -> Self {
+        Self::with_buffer_size(hasher, reader, limits, DEFAULT_BUFFER_SIZE)
+    }
+
+    pub fn with_buffer_size(
+        hasher: Hash,
+        reader: Reader,
+        limits: Limit,
+        buffer_size: usize,
+    ) -> Self {
+        assert!(buffer_size > 0, "the buffer must not be empty");
+
+        Self {
+            assembler: Assembler::start(hasher, limits),
+            buffer: std::vec![0; buffer_size],
+            begin: 0,
+            end: 0,
+            halt: false,
+            reader,
+        }
+    }
+}
+
+impl<Hash: Hasher, Reader: Read, Limit: Limits> Iterator for ReadChunks<Hash, Reader, Limit>
+where
+    Hash::Checksum: Leveled,
+    Hash::State: Clone,
+{
+    type Item = io::Result<ResumableChunk<'static, Hash>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while !self.halt {
+            if self.begin == self.end {
+                match self.reader.read(&mut self.buffer) {
+                    Ok(0) => {
+                        self.halt = true;
+                        break;
+                    }
+                    Ok(n) => {
+                        self.begin = 0;
+                        self.end = n;
+                    }
+                    Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                    Err(e) => return Some(Err(e)),
+                }
+            }
+
+            let (consumed, chunk) = self.assembler.push(&self.buffer[self.begin..self.end]);
+            self.begin += consumed;
+
+            if let Some(chunk) = chunk {
+                return Some(Ok(chunk));
+            }
+        }
+
+        self.assembler.finish().map(Ok)
+    }
+}
+
+/// Collects bytes pushed in pieces into owned chunks.
+pub(crate) struct Assembler<Hash: Hasher, Limit> {
+    scanner: Scanner<Hash, Limit>,
+    preparing: Vec<u8>,
+}
+
+impl<Hash: Hasher, Limit: Limits> Assembler<Hash, Limit>
+where
+    Hash::Checksum: Leveled,
+    Hash::State: Clone,
+{
+    pub(crate) fn start(hasher: Hash, limits: Limit) -> Self {
+        Self {
+            scanner: Scanner::start(hasher, limits),
+            preparing: Vec::new(),
+        }
+    }
+
+    /// Consumes bytes from the front of `data` up to and including the next
+    /// chunk boundary, returning how many bytes were consumed and the chunk
+    /// that ends at the boundary if one was found.
+    pub(crate) fn push(&mut self, data: &[u8]) -> (usize, Option<ResumableChunk<'static, Hash>>) {
+        let (consumed, boundary) = self.scanner.scan(data);
+        self.preparing.extend_from_slice(&data[..consumed]);
+
+        (consumed, boundary.map(|bd| self.take_chunk(bd)))
+    }
+
+    pub(crate) fn finish(&mut self) -> Option<ResumableChunk<'static, Hash>> {
+        self.scanner.finish().map(|bd| self.take_chunk(bd))
+    }
+
+    fn take_chunk(&mut self, boundary: Boundary<Hash>) -> ResumableChunk<'static, Hash> {
+        let reserve = reserve(self.scanner.limits());
+
+        ResumableChunk::at_boundary(
+            core::mem::replace(&mut self.preparing, Vec::with_capacity(reserve)),
+            boundary,
+        )
+    }
+}
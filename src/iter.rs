@@ -1,28 +1,105 @@
 #[cfg(feature = "alloc")]
 use crate::chunk::ResumableChunk;
+use crate::config::Limits;
+#[cfg(feature = "alloc")]
+use crate::thin::Thinned;
 #[allow(unused)]
 use crate::util::*;
-use crate::{Hasher, Leveled, WINDOW_SIZE};
+use crate::{Hasher, Leveled, Memoryless, Ring, Windowless};
 
 #[cfg(feature = "alloc")]
 use alloc::vec::Vec;
+#[cfg(feature = "alloc")]
+use core::convert::TryInto;
 use core::num::NonZeroUsize;
 
+/// The last [`Ring::LEN`] bytes of the input, initially zero.
+struct Window<R> {
+    begin: usize,
+    ring: R,
+}
+
+impl<R: Ring> Window<R> {
+    const fn new() -> Self {
+        Self {
+            begin: 0,
+            ring: R::ZEROED,
+        }
+    }
+
+    /// Adds a byte to the window, returning the byte that leaves it.
+    fn push(&mut self, byte: u8) -> u8 {
+        let old_byte = core::mem::replace(&mut self.ring.as_mut()[self.begin], byte);
+        self.begin += 1;
+        if self.begin == R::LEN {
+            self.begin = 0;
+        }
+
+        old_byte
+    }
+
+    fn clear(&mut self) {
+        *self = Self::new();
+    }
+
+    /// The bytes in the window, oldest first.
+    fn bytes(&mut self) -> &[u8] {
+        self.ring.as_mut().rotate_left(self.begin);
+        self.begin = 0;
+
+        self.ring.as_ref()
+    }
+
+    /// A window holding the last bytes of `tail`, after zeros if there are
+    /// fewer than [`Ring::LEN`] of them.
+    fn with_tail(tail: &[u8]) -> Self {
+        let mut ring = R::ZEROED;
+        let len = tail.len().min(R::LEN);
+        ring.as_mut()[R::LEN - len..].copy_from_slice(&tail[tail.len() - len..]);
+
+        Self { begin: 0, ring }
+    }
+}
+
+/// The byte that leaves the window when the byte of `data` at `position`
+/// enters it, if the window holds the bytes of `data` from `floor` on after
+/// zeros.
+#[cfg(feature = "alloc")]
+pub(crate) fn leaving<R: Ring>(data: &[u8], floor: usize, position: usize) -> u8 {
+    if position >= floor + R::LEN {
+        data[position - R::LEN]
+    } else {
+        0
+    }
+}
+
 pub struct Rolling<Hash: Hasher, Source> {
     hasher: Hash,
     state: Hash::State,
-    begin: usize,
-    ring: [u8; WINDOW_SIZE],
+    window: Window<Hash::Ring>,
     pub source: Source,
 }
 
-impl<Hash: Hasher, Source: Iterator<Item = u8>> Rolling<Hash, Source> {
+impl<Hash: Hasher, Source> Rolling<Hash, Source> {
     pub fn start(hasher: Hash, source: Source) -> Self {
         Self {
             hasher,
             state: Hash::INITIAL_STATE,
-            begin: 0,
-            ring: [0; WINDOW_SIZE],
+            window: Window::new(),
+            source,
+        }
+    }
+
+    /// Picks up hashing where another `Rolling` left off, given its state and
+    /// the bytes it was last fed.
+    ///
+    /// Only the last [`Ring::LEN`] bytes of `tail` are used. If there are
+    /// fewer, they must be all the bytes fed so far.
+    pub fn resume(hasher: Hash, source: Source, state: Hash::State, tail: &[u8]) -> Self {
+        Self {
+            hasher,
+            state,
+            window: Window::with_tail(tail),
             source,
         }
     }
@@ -30,18 +107,22 @@ impl<Hash: Hasher, Source: Iterator<Item = u8>> Rolling<Hash, Source> {
     fn feed(&mut self, byte: u8) -> Hash::Checksum {
         let prev_state = core::mem::replace(&mut self.state, Hash::INITIAL_STATE);
 
-        let (sum, new_state) = self
-            .hasher
-            .process_byte(prev_state, self.ring[self.begin], byte);
+        let old_byte = self.window.push(byte);
+        let (sum, new_state) = self.hasher.process_byte(prev_state, old_byte, byte);
         self.state = new_state;
-        self.ring[self.begin] = byte;
-        self.begin += 1;
-        if self.begin == WINDOW_SIZE {
-            self.begin = 0;
-        }
 
         sum
     }
+
+    /// The state after the bytes fed so far, for [`resume`](Self::resume).
+    pub fn state(&self) -> &Hash::State {
+        &self.state
+    }
+
+    pub fn reset(&mut self) {
+        self.state = Hash::INITIAL_STATE;
+        self.window.clear();
+    }
 }
 
 impl<Hash: Hasher, Source: Iterator<Item = u8>> Iterator for Rolling<Hash, Source> {
@@ -77,6 +158,23 @@ pub enum Boundary<Hash: Hasher> {
 }
 
 impl<Hash: Hasher> Boundary<Hash> {
+    /// The level of the checksum that ended the chunk, if that is what ended
+    /// it.
+    pub fn level(&self) -> Option<u32> {
+        match self {
+            Self::Level(lev, _) => Some(*lev),
+            Self::Capped(_) | Self::Eof(_) => None,
+        }
+    }
+
+    pub fn state(&self) -> &Hash::State {
+        match self {
+            Self::Level(_, state) => state,
+            Self::Capped(state) => state,
+            Self::Eof(state) => state,
+        }
+    }
+
     pub fn into_state(self) -> Hash::State {
         match self {
             Self::Level(_, state) => state,
@@ -91,53 +189,331 @@ pub enum Event<Hash: Hasher> {
     Boundary(Boundary<Hash>),
 }
 
-pub struct Delimited<
-    Hash: Hasher,
-    Source,
-    const THRESHOLD: u32,
-    const MIN_SIZE: usize,
-    const MAX_SIZE: usize,
-> {
-    prepared: Option<(Option<u32>, Hash::State)>,
+/// Decides where chunks end.
+///
+/// Every chunker in this crate makes its decisions here, so that they all
+/// find the same boundaries with the same states. The caller supplies the
+/// byte leaving the window along with each new byte.
+pub(crate) struct Cutter<Hash: Hasher, Limit> {
+    hasher: Hash,
+    limits: Limit,
+    state: Hash::State,
     counter: usize,
-    halt: bool,
-    pub input: WithRolling<Hash, Source>,
 }
 
-impl<
-        Hash: Hasher,
-        Source: Iterator<Item = u8>,
-        const THRESHOLD: u32,
-        const MIN_SIZE: usize,
-        const MAX_SIZE: usize,
-    > Delimited<Hash, Source, THRESHOLD, MIN_SIZE, MAX_SIZE>
+impl<Hash: Hasher, Limit> Cutter<Hash, Limit> {
+    pub(crate) fn start(hasher: Hash, limits: Limit) -> Self {
+        Self {
+            hasher,
+            limits,
+            state: Hash::INITIAL_STATE,
+            counter: 0,
+        }
+    }
+
+    /// Picks up a chunk that already has `counter` bytes, hashed into `state`.
+    pub(crate) fn resume(hasher: Hash, limits: Limit, state: Hash::State, counter: usize) -> Self {
+        Self {
+            hasher,
+            limits,
+            state,
+            counter,
+        }
+    }
+
+    /// Picks up chunking after a boundary, at which the hasher was in `state`
+    /// and the input ended with `tail`.
+    fn after_boundary(
+        hasher: Hash,
+        limits: Limit,
+        state: Hash::State,
+        tail: &[u8],
+    ) -> (Self, Window<Hash::Ring>) {
+        if hasher.resets_at_boundary() {
+            (Self::start(hasher, limits), Window::new())
+        } else {
+            (
+                Self::resume(hasher, limits, state, 0),
+                Window::with_tail(tail),
+            )
+        }
+    }
+
+    pub(crate) fn limits(&self) -> &Limit {
+        &self.limits
+    }
+
+    pub(crate) fn state(&self) -> &Hash::State {
+        &self.state
+    }
+
+    /// Whether the window must be cleared after each boundary.
+    pub(crate) fn resets(&self) -> bool {
+        self.hasher.resets_at_boundary()
+    }
+}
+
+impl<Hash: Memoryless, Limit> Cutter<Hash, Limit> {
+    /// Restarts the hash from a window holding the last bytes of `window`,
+    /// after zeros if there are fewer than [`Ring::LEN`] of them.
+    ///
+    /// This is the state after any input that ends with those bytes, since the
+    /// hasher is [`Memoryless`].
+    pub(crate) fn warm(&mut self, window: &[u8]) {
+        let window = &window[window.len().saturating_sub(<Hash::Ring as Ring>::LEN)..];
+        let (_, state) = self
+            .hasher
+            .process_sequence(Hash::INITIAL_STATE, window.iter().map(|&byte| (0, byte)));
+        self.state = state;
+    }
+
+    /// Counts `count` bytes into the current chunk, none of which may be able
+    /// to end it, but hashes only `window`, the last of them. See
+    /// [`warm`](Self::warm).
+    pub(crate) fn skip(&mut self, count: usize, window: &[u8]) {
+        self.counter += count;
+        self.warm(window);
+    }
+}
+
+impl<Hash: Hasher, Limit: Limits> Cutter<Hash, Limit>
+where
+    Hash::Checksum: Leveled,
+    Hash::State: Clone,
 {
-    pub fn start(hasher: Hash, source: Source) -> Self {
+    /// Hashes one byte of the current chunk, returning the chunk if the byte
+    /// ends it.
+    pub(crate) fn step(&mut self, old_byte: u8, new_byte: u8) -> Option<Extend<Hash>> {
+        let prev_state = core::mem::replace(&mut self.state, Hash::INITIAL_STATE);
+        let (sum, new_state) = self.hasher.process_byte(prev_state, old_byte, new_byte);
+        self.state = new_state;
+        self.counter += 1;
+
+        let lev = sum.level();
+        let boundary = if lev >= self.limits.threshold(self.counter)
+            && self.counter >= self.limits.min_size()
+        {
+            Boundary::Level(lev, self.state.clone())
+        } else if self.counter == self.limits.max_size() {
+            Boundary::Capped(self.state.clone())
+        } else {
+            return None;
+        };
+
+        if self.resets() {
+            self.state = Hash::INITIAL_STATE;
+        }
+
+        Some(Extend {
+            length: NonZeroUsize::new(core::mem::replace(&mut self.counter, 0))?,
+            boundary,
+        })
+    }
+
+    /// Like [`step`](Self::step), taking the byte leaving the window from
+    /// `window`.
+    fn feed(&mut self, window: &mut Window<Hash::Ring>, byte: u8) -> Option<Extend<Hash>> {
+        let old_byte = window.push(byte);
+        let ext = self.step(old_byte, byte)?;
+        if self.resets() {
+            window.clear();
+        }
+
+        Some(ext)
+    }
+
+    /// Like [`step`](Self::step), for the byte of `data` at `position`, when
+    /// the window holds the bytes of `data` from `floor` on after zeros.
+    #[cfg(feature = "alloc")]
+    pub(crate) fn step_at(
+        &mut self,
+        data: &[u8],
+        floor: usize,
+        position: usize,
+    ) -> Option<Extend<Hash>> {
+        let old_byte = leaving::<Hash::Ring>(data, floor, position);
+
+        self.step(old_byte, data[position])
+    }
+
+    /// How many of the next bytes cannot end the current chunk.
+    pub(crate) fn quiet_len(&self) -> usize {
+        let quiet = self.limits.min_size().saturating_sub(self.counter + 1);
+        let max_size = self.limits.max_size();
+        if self.counter < max_size {
+            quiet.min(max_size - self.counter - 1)
+        } else {
+            quiet
+        }
+    }
+
+    /// Hashes a block of bytes, none of which may be able to end the current
+    /// chunk.
+    #[cfg(feature = "alloc")]
+    pub(crate) fn step_block<Block: AsRef<[u8]>>(&mut self, old_block: &[u8], new_block: &Block)
+    where
+        Hash: Thinned<Block>,
+    {
+        let prev_state = core::mem::replace(&mut self.state, Hash::INITIAL_STATE);
+        let (_, new_state) = self.hasher.process_block(prev_state, old_block, new_block);
+        self.state = new_state;
+        self.counter += new_block.as_ref().len();
+    }
+
+    /// Hashes the bytes of `new_bytes`, with the bytes leaving the window in
+    /// `old_bytes`, until one of them ends the current chunk. Returns how
+    /// many were hashed, and the chunk if it ended.
+    ///
+    /// The state is kept out of `self` in between, which makes this quicker
+    /// than calling [`step`](Self::step) for each byte.
+    #[cfg(feature = "alloc")]
+    pub(crate) fn scan(
+        &mut self,
+        old_bytes: &[u8],
+        new_bytes: &[u8],
+    ) -> (usize, Option<Extend<Hash>>) {
+        let mut state = core::mem::replace(&mut self.state, Hash::INITIAL_STATE);
+        let mut counter = self.counter;
+        let (min_size, max_size) = (self.limits.min_size(), self.limits.max_size());
+
+        for (i, (&old_byte, &new_byte)) in old_bytes.iter().zip(new_bytes).enumerate() {
+            let (sum, new_state) = self.hasher.process_byte(state, old_byte, new_byte);
+            state = new_state;
+            counter += 1;
+
+            let lev = sum.level();
+            let boundary = if lev >= self.limits.threshold(counter) && counter >= min_size {
+                Boundary::Level(lev, state.clone())
+            } else if counter == max_size {
+                Boundary::Capped(state.clone())
+            } else {
+                continue;
+            };
+
+            self.state = if self.resets() {
+                Hash::INITIAL_STATE
+            } else {
+                state
+            };
+            self.counter = 0;
+
+            return (
+                i + 1,
+                NonZeroUsize::new(counter).map(|length| Extend { length, boundary }),
+            );
+        }
+
+        self.state = state;
+        self.counter = counter;
+
+        (old_bytes.len().min(new_bytes.len()), None)
+    }
+
+    /// Signals the end of the input, returning the last chunk unless it is
+    /// empty.
+    pub(crate) fn finish(&mut self) -> Option<Extend<Hash>> {
+        Some(Extend {
+            length: NonZeroUsize::new(core::mem::replace(&mut self.counter, 0))?,
+            boundary: Boundary::Eof(self.state.clone()),
+        })
+    }
+}
+
+/// The bytes of the input, each followed by the chunk boundary it ends if
+/// any.
+///
+/// The events always end with [`Boundary::Eof`], even when the input is
+/// empty or its last byte ends a chunk.
+pub struct Delimited<Hash: Hasher, Source, Limit> {
+    cutter: Cutter<Hash, Limit>,
+    window: Window<Hash::Ring>,
+    prepared: Option<Boundary<Hash>>,
+    halt: bool,
+    pub source: Source,
+}
+
+impl<Hash: Hasher, Source: Iterator<Item = u8>, Limit: Limits> Delimited<Hash, Source, Limit> {
+    pub fn start(hasher: Hash, source: Source, limits: Limit) -> Self {
         Self {
+            cutter: Cutter::start(hasher, limits),
+            window: Window::new(),
+            prepared: None,
+            halt: false,
+            source,
+        }
+    }
+
+    /// Picks up chunking after a chunk boundary, to chunk the bytes that come
+    /// after it without going over the ones before.
+    ///
+    /// `state` is the state at the boundary, as kept by a
+    /// `chunk::ResumableChunk`, and `tail` holds the input up to the boundary.
+    /// Only the last [`Ring::LEN`] bytes of `tail` are used; if there are
+    /// fewer, they must be the whole input so far.
+    ///
+    /// The last chunk of an input ends with the input rather than at a
+    /// boundary, so to chunk an input that has grown, resume after the chunk
+    /// before it and feed its bytes again.
+    ///
+    /// ```
+    /// # #[cfg(feature = "alloc")]
+    /// # fn main() {
+    /// use hashsplit::algorithms::Cp32;
+    /// use hashsplit::iter::Event;
+    /// use hashsplit::Config;
+    ///
+    /// let cfg: Config<Cp32, 8, 64, 1024> = Default::default();
+    /// let mut data = vec![0; 10_000];
+    ///
+    /// let chunks: Vec<_> = cfg.spans(&data).collect();
+    /// let kept = &chunks[..chunks.len() - 1];
+    /// let offset: usize = kept.iter().map(|chunk| chunk.len()).sum();
+    /// let state = kept[kept.len() - 1].state;
+    ///
+    /// data.extend_from_slice(&[1; 5000]);
+    /// let events = cfg.resume_delimited(data[offset..].iter().copied(), state, &data[..offset]);
+    ///
+    /// let fed = events.filter(|ev| matches!(ev, Event::Data(_))).count();
+    /// assert_eq!(fed, data.len() - offset);
+    /// # }
+    /// # #[cfg(not(feature = "alloc"))]
+    /// # fn main() {}
+    /// ```
+    pub fn resume(
+        hasher: Hash,
+        source: Source,
+        limits: Limit,
+        state: Hash::State,
+        tail: &[u8],
+    ) -> Self {
+        let (cutter, window) = Cutter::after_boundary(hasher, limits, state, tail);
+
+        Self {
+            cutter,
+            window,
             prepared: None,
-            counter: 0,
             halt: false,
-            input: WithRolling(Rolling::start(hasher, source)),
+            source,
         }
     }
 
+    /// The state of the hasher after the bytes taken from the source so far.
+    pub fn state(&self) -> &Hash::State {
+        self.cutter.state()
+    }
+
     #[cfg(feature = "alloc")]
     pub fn splits(self) -> Splits<Self> {
         Splits {
-            reserve: 2 * MIN_SIZE, // XXX
+            reserve: reserve(self.cutter.limits()),
             preparing: None,
             source: self,
         }
     }
 }
 
-impl<
-        Hash: Hasher,
-        Source: Iterator<Item = u8>,
-        const THRESHOLD: u32,
-        const MIN_SIZE: usize,
-        const MAX_SIZE: usize,
-    > Iterator for Delimited<Hash, Source, THRESHOLD, MIN_SIZE, MAX_SIZE>
+impl<Hash: Hasher, Source: Iterator<Item = u8>, Limit: Limits> Iterator
+    for Delimited<Hash, Source, Limit>
 where
     Hash::Checksum: Leveled,
     Hash::State: Clone,
@@ -149,35 +525,49 @@ where
             return None;
         }
 
-        if let Some((may, state)) = self.prepared.take() {
-            return Some(Event::Boundary(if let Some(lev) = may {
-                Boundary::Level(lev, state)
-            } else {
-                Boundary::Capped(state)
-            }));
+        if let Some(bd) = self.prepared.take() {
+            return Some(Event::Boundary(bd));
         }
 
-        if let Some((byte, sum)) = self.input.next() {
-            self.counter += 1;
-
-            let lev = sum.level();
-            if lev >= THRESHOLD && self.counter >= MIN_SIZE {
-                self.prepared = Some((Some(lev), self.input.state().clone()));
-                self.counter = 0;
-            } else if self.counter == MAX_SIZE {
-                self.prepared = Some((None, self.input.state().clone()));
-                self.counter = 0;
-            }
+        if let Some(byte) = self.source.next() {
+            self.prepared = self
+                .cutter
+                .feed(&mut self.window, byte)
+                .map(|ext| ext.boundary);
 
             return Some(Event::Data(byte));
         }
 
         self.halt = true;
+        self.cutter.finish();
+
+        Some(Event::Boundary(Boundary::Eof(self.cutter.state().clone())))
+    }
+}
 
-        Some(Event::Boundary(Boundary::Eof(self.input.state().clone())))
+/// How many bytes to set aside for a chunk that is being collected: the
+/// minimum size, plus the `2^threshold` bytes it takes on average for a
+/// checksum to reach the threshold after that, but no more than the maximum
+/// size or [`MAX_RESERVE`]. Longer chunks grow their buffers as usual.
+#[cfg(feature = "alloc")]
+pub(crate) fn reserve<Limit: Limits>(limits: &Limit) -> usize {
+    let min_size = limits.min_size();
+    let expected = 1usize
+        .checked_shl(limits.threshold(min_size))
+        .map_or(usize::MAX, |distance| min_size.saturating_add(distance));
+
+    match limits.max_size() {
+        0 => expected,
+        max_size => expected.min(max_size),
     }
+    .min(MAX_RESERVE)
 }
 
+/// The most bytes set aside up front for a chunk, so that a high threshold
+/// does not tie up memory that most chunks would not use.
+#[cfg(feature = "alloc")]
+const MAX_RESERVE: usize = 1 << 20;
+
 #[cfg(feature = "alloc")]
 #[doc(cfg(feature = "alloc"))]
 pub struct Splits<Source> {
@@ -192,7 +582,7 @@ impl<Hash: Hasher, Source: Iterator<Item = Event<Hash>>> Iterator for Splits<Sou
     type Item = ResumableChunk<'static, Hash>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        while let Some(ev) = self.source.next() {
+        for ev in self.source.by_ref() {
             match ev {
                 Event::Data(byte) => {
                     let reserve = self.reserve;
@@ -205,7 +595,7 @@ impl<Hash: Hasher, Source: Iterator<Item = Event<Hash>>> Iterator for Splits<Sou
                     return self
                         .preparing
                         .take()
-                        .map(|prep| ResumableChunk::new(prep, bd.into_state()));
+                        .map(|prep| ResumableChunk::at_boundary(prep, bd));
                 }
             }
         }
@@ -214,54 +604,98 @@ impl<Hash: Hasher, Source: Iterator<Item = Event<Hash>>> Iterator for Splits<Sou
     }
 }
 
+/// Finds chunk boundaries in data that arrives in pieces, for instance from a
+/// reader.
+pub struct Scanner<Hash: Hasher, Limit> {
+    cutter: Cutter<Hash, Limit>,
+    window: Window<Hash::Ring>,
+}
+
+impl<Hash: Hasher, Limit: Limits> Scanner<Hash, Limit>
+where
+    Hash::Checksum: Leveled,
+    Hash::State: Clone,
+{
+    pub fn start(hasher: Hash, limits: Limit) -> Self {
+        Self {
+            cutter: Cutter::start(hasher, limits),
+            window: Window::new(),
+        }
+    }
+
+    /// Consumes bytes from the front of `data` up to and including the next
+    /// chunk boundary, returning how many bytes were consumed and the boundary
+    /// if one was found.
+    pub fn scan(&mut self, data: &[u8]) -> (usize, Option<Boundary<Hash>>) {
+        for (i, &byte) in data.iter().enumerate() {
+            if let Some(ext) = self.cutter.feed(&mut self.window, byte) {
+                return (i + 1, Some(ext.boundary));
+            }
+        }
+
+        (data.len(), None)
+    }
+
+    /// Signals the end of the input, returning the final boundary if any bytes
+    /// were consumed since the last one.
+    pub fn finish(&mut self) -> Option<Boundary<Hash>> {
+        self.cutter.finish().map(|ext| ext.boundary)
+    }
+
+    pub fn limits(&self) -> &Limit {
+        self.cutter.limits()
+    }
+}
+
 pub struct Extend<Hash: Hasher> {
     pub length: NonZeroUsize,
     pub boundary: Boundary<Hash>,
 }
 
-pub struct Distances<
-    Hash: Hasher,
-    Source,
-    const THRESHOLD: u32,
-    const MIN_SIZE: usize,
-    const MAX_SIZE: usize,
-> {
-    counter: usize,
+pub struct Distances<Hash: Hasher, Source, Limit> {
+    cutter: Cutter<Hash, Limit>,
+    window: Window<Hash::Ring>,
     halt: bool,
-    pub input: Rolling<Hash, Source>,
+    pub source: Source,
 }
 
-impl<
-        Hash: Hasher,
-        Source: Iterator<Item = u8>,
-        const THRESHOLD: u32,
-        const MIN_SIZE: usize,
-        const MAX_SIZE: usize,
-    > Distances<Hash, Source, THRESHOLD, MIN_SIZE, MAX_SIZE>
-{
-    pub fn start(hasher: Hash, source: Source) -> Self {
+impl<Hash: Hasher, Source: Iterator<Item = u8>, Limit: Limits> Distances<Hash, Source, Limit> {
+    pub fn start(hasher: Hash, source: Source, limits: Limit) -> Self {
         Self {
-            counter: 0,
+            cutter: Cutter::start(hasher, limits),
+            window: Window::new(),
             halt: false,
-            input: Rolling::start(hasher, source),
+            source,
         }
     }
 
-    fn yield_extend(&mut self, boundary: Boundary<Hash>) -> Option<Extend<Hash>> {
-        Some(Extend {
-            length: NonZeroUsize::new(core::mem::replace(&mut self.counter, 0))?,
-            boundary,
-        })
+    /// The state of the hasher after the bytes taken from the source so far.
+    pub fn state(&self) -> &Hash::State {
+        self.cutter.state()
+    }
+
+    /// Picks up chunking after a chunk boundary, like
+    /// [`Delimited::resume`].
+    pub fn resume(
+        hasher: Hash,
+        source: Source,
+        limits: Limit,
+        state: Hash::State,
+        tail: &[u8],
+    ) -> Self {
+        let (cutter, window) = Cutter::after_boundary(hasher, limits, state, tail);
+
+        Self {
+            cutter,
+            window,
+            halt: false,
+            source,
+        }
     }
 }
 
-impl<
-        Hash: Hasher,
-        Source: Iterator<Item = u8>,
-        const THRESHOLD: u32,
-        const MIN_SIZE: usize,
-        const MAX_SIZE: usize,
-    > Iterator for Distances<Hash, Source, THRESHOLD, MIN_SIZE, MAX_SIZE>
+impl<Hash: Hasher, Source: Iterator<Item = u8>, Limit: Limits> Iterator
+    for Distances<Hash, Source, Limit>
 where
     Hash::Checksum: Leveled,
     Hash::State: Clone,
@@ -273,59 +707,125 @@ where
             return None;
         }
 
-        while let Some(sum) = self.input.next() {
-            self.counter += 1;
+        for byte in self.source.by_ref() {
+            if let Some(ext) = self.cutter.feed(&mut self.window, byte) {
+                return Some(ext);
+            }
+        }
 
-            let lev = sum.level();
-            if lev >= THRESHOLD && self.counter >= MIN_SIZE {
-                return self.yield_extend(Boundary::Level(lev, self.input.state.clone()));
-            } else if self.counter == MAX_SIZE {
-                return self.yield_extend(Boundary::Capped(self.input.state.clone()));
+        self.halt = true;
+
+        self.cutter.finish()
+    }
+}
+
+/// Chunk boundaries in the style of FastCDC.
+///
+/// This is meant to be used with [`Normalized`](crate::config::Normalized)
+/// limits, whose threshold is stricter for short chunks than for long ones.
+/// Since the hasher is [`Windowless`], the bytes leaving the window are not
+/// needed, and since it is [`Memoryless`], of the bytes that come before
+/// `MIN_SIZE`, only the last [`Ring::LEN`] are hashed.
+///
+/// ```
+/// use hashsplit::algorithms::Gear64;
+/// use hashsplit::Config;
+///
+/// let cfg: Config<Gear64, 10, 256, 4096> = Default::default();
+/// let data = [0; 10_000];
+///
+/// let lengths: Vec<usize> = cfg
+///     .fast_cdc::<1024, _>(data.iter().copied())
+///     .map(|ext| ext.length.get())
+///     .collect();
+///
+/// assert_eq!(lengths.iter().sum::<usize>(), data.len());
+/// ```
+pub struct FastCdc<Hash: Hasher, Source, Limit> {
+    cutter: Cutter<Hash, Limit>,
+    halt: bool,
+    pub source: Source,
+}
+
+impl<Hash: Windowless + Memoryless, Source: Iterator<Item = u8>, Limit: Limits>
+    FastCdc<Hash, Source, Limit>
+{
+    pub fn start(hasher: Hash, source: Source, limits: Limit) -> Self {
+        Self {
+            cutter: Cutter::start(hasher, limits),
+            halt: false,
+            source,
+        }
+    }
+}
+
+impl<Hash: Windowless + Memoryless, Source: Iterator<Item = u8>, Limit: Limits> Iterator
+    for FastCdc<Hash, Source, Limit>
+where
+    Hash::Checksum: Leveled,
+    Hash::State: Clone,
+{
+    type Item = Extend<Hash>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.halt {
+            return None;
+        }
+
+        let quiet = self.cutter.quiet_len();
+        if quiet > <Hash::Ring as Ring>::LEN {
+            // The window is kept only in case the input ends before the chunk
+            // can.
+            let mut window = Window::<Hash::Ring>::new();
+            let mut count = 0;
+            for byte in self.source.by_ref().take(quiet) {
+                window.push(byte);
+                count += 1;
+            }
+
+            let bytes = window.bytes();
+            if count >= bytes.len() {
+                self.cutter.skip(count, bytes);
+            } else {
+                for &byte in &bytes[bytes.len() - count..] {
+                    self.cutter.step(0, byte);
+                }
+            }
+        }
+
+        for byte in self.source.by_ref() {
+            if let Some(ext) = self.cutter.step(0, byte) {
+                return Some(ext);
             }
         }
 
         self.halt = true;
 
-        self.yield_extend(Boundary::Eof(self.input.state.clone()))
+        self.cutter.finish()
     }
 }
 
 #[cfg(feature = "alloc")]
 #[doc(cfg(feature = "alloc"))]
-pub struct Spans<
-    'a,
-    Hash: Hasher,
-    const THRESHOLD: u32,
-    const MIN_SIZE: usize,
-    const MAX_SIZE: usize,
-> {
+pub struct Spans<'a, Hash: Hasher, Limit> {
     saved: &'a [u8],
-    distances: Distances<
-        Hash,
-        core::iter::Copied<core::slice::Iter<'a, u8>>,
-        THRESHOLD,
-        MIN_SIZE,
-        MAX_SIZE,
-    >,
+    distances: Distances<Hash, core::iter::Copied<core::slice::Iter<'a, u8>>, Limit>,
 }
 
 #[cfg(feature = "alloc")]
 #[doc(cfg(feature = "alloc"))]
-impl<'a, Hash: Hasher, const THRESHOLD: u32, const MIN_SIZE: usize, const MAX_SIZE: usize>
-    Spans<'a, Hash, THRESHOLD, MIN_SIZE, MAX_SIZE>
-{
-    pub fn start(hasher: Hash, data: &'a [u8]) -> Self {
+impl<'a, Hash: Hasher, Limit: Limits> Spans<'a, Hash, Limit> {
+    pub fn start(hasher: Hash, data: &'a [u8], limits: Limit) -> Self {
         Self {
             saved: data,
-            distances: Distances::start(hasher, data.iter().copied()),
+            distances: Distances::start(hasher, data.iter().copied(), limits),
         }
     }
 }
 
 #[cfg(feature = "alloc")]
 #[doc(cfg(feature = "alloc"))]
-impl<'a, Hash: Hasher, const THRESHOLD: u32, const MIN_SIZE: usize, const MAX_SIZE: usize> Iterator
-    for Spans<'a, Hash, THRESHOLD, MIN_SIZE, MAX_SIZE>
+impl<'a, Hash: Hasher, Limit: Limits> Iterator for Spans<'a, Hash, Limit>
 where
     Hash::Checksum: Leveled,
     Hash::State: Clone,
@@ -337,7 +837,219 @@ where
             let prev = self.saved;
             self.saved = &prev[length.get()..];
 
-            ResumableChunk::new(&prev[..length.get()], boundary.into_state())
+            ResumableChunk::at_boundary(&prev[..length.get()], boundary)
         })
     }
 }
+
+/// The same chunks as [`Spans`], found by indexing into the slice.
+///
+/// The byte leaving the window is read straight from the slice, so no ring
+/// buffer is kept, and since no chunk can end before it has `MIN_SIZE` bytes,
+/// only the last [`Ring::LEN`] of those are hashed, which the hasher allows by
+/// being [`Memoryless`].
+///
+/// ```
+/// use hashsplit::algorithms::Cp32;
+/// use hashsplit::Config;
+///
+/// let cfg: Config<Cp32, 8, 512, 4096> = Default::default();
+/// let data = [0; 10_000];
+///
+/// let lengths: Vec<usize> = cfg.indexed_spans(&data).map(|chunk| chunk.len()).collect();
+///
+/// assert_eq!(lengths.iter().sum::<usize>(), data.len());
+/// ```
+#[cfg(feature = "alloc")]
+#[doc(cfg(feature = "alloc"))]
+pub struct IndexedSpans<'a, Hash: Hasher, Limit> {
+    cutter: Cutter<Hash, Limit>,
+    data: &'a [u8],
+    begin: usize,
+    position: usize,
+    floor: usize,
+}
+
+#[cfg(feature = "alloc")]
+#[doc(cfg(feature = "alloc"))]
+impl<'a, Hash: Memoryless, Limit: Limits> IndexedSpans<'a, Hash, Limit> {
+    pub fn start(hasher: Hash, data: &'a [u8], limits: Limit) -> Self {
+        Self {
+            cutter: Cutter::start(hasher, limits),
+            data,
+            begin: 0,
+            position: 0,
+            floor: 0,
+        }
+    }
+
+    fn yield_chunk(&mut self, ext: Extend<Hash>) -> ResumableChunk<'a, Hash> {
+        let chunk = &self.data[self.begin..self.position];
+        self.begin = self.position;
+        if self.cutter.resets() {
+            self.floor = self.position;
+        }
+
+        ResumableChunk::at_boundary(chunk, ext.boundary)
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[doc(cfg(feature = "alloc"))]
+impl<'a, Hash: Memoryless, Limit: Limits> Iterator for IndexedSpans<'a, Hash, Limit>
+where
+    Hash::Checksum: Leveled,
+    Hash::State: Clone,
+{
+    type Item = ResumableChunk<'a, Hash>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let data = self.data;
+        if self.begin == data.len() {
+            return None;
+        }
+
+        let window = <Hash::Ring as Ring>::LEN;
+        let quiet_end = (self.position + self.cutter.quiet_len()).min(data.len());
+        if quiet_end > self.position + window {
+            self.cutter.skip(
+                quiet_end - self.position,
+                &data[quiet_end - window..quiet_end],
+            );
+            self.position = quiet_end;
+        }
+
+        while self.position < data.len() {
+            let position = self.position;
+            self.position += 1;
+
+            if let Some(ext) = self.cutter.step_at(data, self.floor, position) {
+                return Some(self.yield_chunk(ext));
+            }
+        }
+
+        self.cutter.finish().map(|ext| self.yield_chunk(ext))
+    }
+}
+
+/// The same chunks as [`Spans`], found by hashing `N` bytes at a time with
+/// [`Thinned::process_block`] wherever none of them can end a chunk.
+///
+/// Every byte is still hashed, but the hasher must be [`Memoryless`] all the
+/// same, so that this can stand in for [`IndexedSpans`]. The bytes that can
+/// end a chunk are hashed in a loop that keeps the state in a local variable
+/// rather than in the iterator, so this is quicker than [`Spans`] even when
+/// `MIN_SIZE` is small compared to `N`.
+///
+/// ```
+/// use hashsplit::algorithms::Rrs1;
+/// use hashsplit::config::DynConfig;
+///
+/// let cfg = DynConfig::new(Rrs1::default(), 8, 512, 4096);
+/// let data = [0; 10_000];
+///
+/// let lengths: Vec<usize> = cfg.thinned_spans::<16>(&data).map(|chunk| chunk.len()).collect();
+///
+/// assert_eq!(lengths.iter().sum::<usize>(), data.len());
+/// ```
+#[cfg(feature = "alloc")]
+#[doc(cfg(feature = "alloc"))]
+pub struct ThinnedSpans<'a, Hash: Hasher, Limit, const N: usize> {
+    cutter: Cutter<Hash, Limit>,
+    data: &'a [u8],
+    begin: usize,
+    position: usize,
+    floor: usize,
+}
+
+#[cfg(feature = "alloc")]
+#[doc(cfg(feature = "alloc"))]
+impl<'a, Hash: Memoryless, Limit: Limits, const N: usize> ThinnedSpans<'a, Hash, Limit, N> {
+    pub fn start(hasher: Hash, data: &'a [u8], limits: Limit) -> Self {
+        Self {
+            cutter: Cutter::start(hasher, limits),
+            data,
+            begin: 0,
+            position: 0,
+            floor: 0,
+        }
+    }
+
+    fn yield_chunk(&mut self, ext: Extend<Hash>) -> ResumableChunk<'a, Hash> {
+        let chunk = &self.data[self.begin..self.position];
+        self.begin = self.position;
+        if self.cutter.resets() {
+            self.floor = self.position;
+        }
+
+        ResumableChunk::at_boundary(chunk, ext.boundary)
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[doc(cfg(feature = "alloc"))]
+impl<'a, Hash: Thinned<[u8; N]> + Memoryless, Limit: Limits, const N: usize> Iterator
+    for ThinnedSpans<'a, Hash, Limit, N>
+where
+    Hash::Checksum: Leveled,
+    Hash::State: Clone,
+{
+    type Item = ResumableChunk<'a, Hash>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let data = self.data;
+        if self.begin == data.len() {
+            return None;
+        }
+
+        let window = <Hash::Ring as Ring>::LEN;
+        if N > 0 {
+            let quiet_end = (self.position + self.cutter.quiet_len()).min(data.len());
+            while self.position + N <= quiet_end {
+                let position = self.position;
+                let new_block = data[position..position + N].try_into().unwrap();
+
+                if position >= self.floor + window {
+                    let old_begin = position - window;
+                    self.cutter
+                        .step_block(&data[old_begin..old_begin + N], new_block);
+                } else {
+                    // Some of the bytes leaving the window are the zeros it
+                    // started with.
+                    let mut old_block = [0; N];
+                    for (i, old_byte) in old_block.iter_mut().enumerate() {
+                        *old_byte = leaving::<Hash::Ring>(data, self.floor, position + i);
+                    }
+                    self.cutter.step_block(&old_block, new_block);
+                }
+
+                self.position += N;
+            }
+        }
+
+        // The bytes leaving the window start out as the zeros it was filled
+        // with.
+        while self.position < data.len().min(self.floor + window) {
+            let position = self.position;
+            self.position += 1;
+
+            if let Some(ext) = self.cutter.step_at(data, self.floor, position) {
+                return Some(self.yield_chunk(ext));
+            }
+        }
+
+        if self.position < data.len() {
+            let position = self.position;
+            let (count, ext) = self
+                .cutter
+                .scan(&data[position - window..], &data[position..]);
+            self.position += count;
+
+            if let Some(ext) = ext {
+                return Some(self.yield_chunk(ext));
+            }
+        }
+
+        self.cutter.finish().map(|ext| self.yield_chunk(ext))
+    }
+}
@@ -1,15 +1,33 @@
 #![no_std]
-#![feature(min_const_generics)]
 #![feature(doc_cfg)]
 
 #[cfg(feature = "alloc")]
 extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
 
 #[allow(unused)]
 use crate::util::*;
 
+/// The window size of the hashers in [`algorithms`] unless they are told
+/// otherwise.
 pub const WINDOW_SIZE: usize = 64;
 
+/// The buffer that holds the window of a [`Hasher`], which is always an array
+/// of bytes.
+pub trait Ring: Copy + AsRef<[u8]> + AsMut<[u8]> {
+    /// The number of bytes in the window, which must be at least one.
+    const LEN: usize;
+
+    const ZEROED: Self;
+}
+
+impl<const N: usize> Ring for [u8; N] {
+    const LEN: usize = N;
+
+    const ZEROED: Self = [0; N];
+}
+
 pub trait Leveled {
     fn level(self) -> u32;
 }
@@ -48,11 +66,20 @@ implement_leveled_for_integer_primitive! {u32}
 implement_leveled_for_integer_primitive! {u64}
 implement_leveled_for_integer_primitive! {u8}
 
+/// A rolling hash over a window of the last [`Ring::LEN`] bytes of the input.
+///
+/// Hashers whose state depends on nothing but the window say so by
+/// implementing [`Memoryless`].
 pub trait Hasher {
     type Checksum: Default;
 
     type State;
 
+    /// Holds the bytes in the window, the last of which is passed as
+    /// `old_byte` when a new byte arrives. Hashers without a reason to choose
+    /// otherwise use a window of [`WINDOW_SIZE`] bytes.
+    type Ring: Ring;
+
     const INITIAL_STATE: Self::State;
 
     fn process_byte(
@@ -74,13 +101,35 @@ pub trait Hasher {
             },
         )
     }
+
+    /// Whether the state and the window go back to their initial values after
+    /// every chunk boundary, instead of rolling on into the next chunk.
+    fn resets_at_boundary(&self) -> bool {
+        false
+    }
 }
 
+/// A [`Hasher`] whose output does not depend on the byte leaving the window.
+///
+/// Iterators may pass any value for `old_byte` to such a hasher, which lets
+/// them avoid keeping the window around.
+pub trait Windowless: Hasher {}
+
+/// A [`Hasher`] whose state depends only on the bytes in its window.
+///
+/// Starting from [`INITIAL_STATE`](Hasher::INITIAL_STATE) and a window of
+/// zeros, the state after any input must depend only on the bytes in the
+/// window, the last [`Ring::LEN`] of the input after those zeros. Chunkers that hash only the window before a byte whose checksum
+/// matters, or that pick up hashing from the window alone, require it. Every
+/// hasher in [`algorithms`] implements it.
+pub trait Memoryless: Hasher {}
+
 pub trait Named: Hasher {
     const NAME: &'static str;
 }
 
 pub(crate) mod util {
+    #[allow(dead_code)]
     pub trait Checkpoint {
         fn check(self) -> Option<()>;
     }
@@ -101,7 +150,26 @@ pub mod algorithms;
 #[doc(cfg(feature = "alloc"))]
 pub mod chunk;
 pub mod config;
+pub mod digest;
+#[cfg(feature = "std")]
+#[doc(cfg(feature = "std"))]
+pub mod io;
 pub mod iter;
+#[cfg(feature = "alloc")]
+#[doc(cfg(feature = "alloc"))]
+pub mod manifest;
+#[cfg(feature = "rayon")]
+#[doc(cfg(feature = "rayon"))]
+pub mod par;
+#[cfg(feature = "alloc")]
+#[doc(cfg(feature = "alloc"))]
+pub mod rechunk;
+#[cfg(feature = "store")]
+#[doc(cfg(feature = "store"))]
+pub mod store;
+#[cfg(feature = "futures")]
+#[doc(cfg(feature = "futures"))]
+pub mod stream;
 pub mod thin;
 
 pub use config::Config;
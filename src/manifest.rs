@@ -0,0 +1,345 @@
+//! A compact binary encoding of an [`Outline`], together with the identifier
+//! of the [`Config`](crate::Config) that chunked the input.
+//!
+//! An encoded manifest consists of:
+//!
+//! - the four bytes of [`MAGIC`], then the [`VERSION`] byte;
+//! - one byte giving the length `n` of each digest;
+//! - the length of the [`Digest::NAME`] of the digest as an integer, then its
+//!   UTF-8 bytes;
+//! - the length of the identifier as an integer, then its UTF-8 bytes;
+//! - the `n` bytes of the digest of the root;
+//! - the root node.
+//!
+//! A node is a kind byte, 0 for a leaf or 1 for an internal node, followed by
+//! the number of its children as an integer. A leaf goes on with the `n`
+//! bytes of each chunk's digest, its length as an integer, and its level plus
+//! one as an integer, or zero if it has none. An internal node goes on with
+//! its children. Integers are unsigned LEB128, at most ten bytes long.
+//!
+//! The digests and lengths of nodes are not stored but computed again when
+//! decoding, and the digest of the root is checked against them. That digest
+//! only covers the digests of the chunks and the shape of the tree: the
+//! identifier and the lengths and levels of the chunks are only checked to be
+//! well-formed, so a manifest whose root digest is trusted can still have
+//! them changed. A `store::TreeReader` catches a wrong length when it
+//! fetches the chunk.
+//!
+//! The identifier must parse as a [`config::Identifier`], for any algorithm
+//! and window, and every node but the root must have children or chunks.
+//!
+//! ```
+//! # #[cfg(feature = "blake3")]
+//! # fn main() {
+//! use hashsplit::algorithms::Cp32;
+//! use hashsplit::chunk::TreeBuilder;
+//! use hashsplit::digest::Blake3;
+//! use hashsplit::manifest::Manifest;
+//! use hashsplit::Config;
+//!
+//! let cfg: Config<Cp32, 8, 64, 1024> = Default::default();
+//! let data = [0; 10_000];
+//! let mut builder = TreeBuilder::<_, Blake3>::new(8);
+//! builder.extend(cfg.spans(&data));
+//!
+//! let manifest = Manifest::<Blake3> {
+//!     identifier: cfg.to_string(),
+//!     outline: builder.finish().unwrap().outline(),
+//! };
+//! let bytes = manifest.encode();
+//!
+//! let decoded = Manifest::<Blake3>::decode(&bytes).unwrap();
+//! assert_eq!(decoded.identifier, "HashSplit_8_cp32_W64_64_1Ki");
+//! # }
+//! # #[cfg(not(feature = "blake3"))]
+//! # fn main() {}
+//! ```
+
+use crate::chunk::{ChunkRef, Outline, OutlineNode};
+use crate::config::{self, ParseError};
+use crate::digest::{self, Digest};
+#[allow(unused)]
+use crate::util::*;
+
+use alloc::boxed::Box;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::convert::TryFrom;
+use core::fmt;
+
+/// The bytes every encoded manifest begins with.
+pub const MAGIC: [u8; 4] = *b"HSMF";
+
+/// The version of the encoding written by [`Manifest::encode`].
+pub const VERSION: u8 = 1;
+
+/// How deeply nodes may be nested in a manifest that is decoded.
+pub const MAX_DEPTH: usize = 256;
+
+const LEAF: u8 = 0;
+
+const INTERNAL: u8 = 1;
+
+/// The outline of an input and the identifier of the configuration that
+/// chunked it.
+pub struct Manifest<D: Digest> {
+    pub identifier: String,
+    pub outline: Outline<D>,
+}
+
+impl<D: Digest> Manifest<D> {
+    /// Encodes the manifest. Unless the identifier is a configuration
+    /// identifier, the result will not decode.
+    pub fn encode(&self) -> Vec<u8> {
+        let root = self.outline.digest().as_ref();
+        let digest_len = u8::try_from(root.len()).expect("digests must be under 256 bytes");
+
+        let mut out = Vec::new();
+        out.extend_from_slice(&MAGIC);
+        out.push(VERSION);
+        out.push(digest_len);
+        write_integer(&mut out, D::NAME.len() as u64);
+        out.extend_from_slice(D::NAME.as_bytes());
+        write_integer(&mut out, self.identifier.len() as u64);
+        out.extend_from_slice(self.identifier.as_bytes());
+        out.extend_from_slice(root);
+        write_node(&mut out, &self.outline.root);
+
+        out
+    }
+
+    pub fn decode(bytes: &[u8]) -> Result<Self, DecodeError>
+    where
+        D::Output: for<'b> TryFrom<&'b [u8]>,
+    {
+        let mut reader = Reader { bytes };
+        if reader.take(MAGIC.len())? != MAGIC {
+            return Err(DecodeError::BadMagic);
+        }
+        let version = reader.byte()?;
+        if version != VERSION {
+            return Err(DecodeError::UnsupportedVersion(version));
+        }
+        let digest_len = reader.byte()?;
+        let name_len = reader.count()?;
+        if reader.take(name_len)? != D::NAME.as_bytes() {
+            return Err(DecodeError::WrongDigest);
+        }
+
+        let identifier_len = reader.count()?;
+        let identifier = core::str::from_utf8(reader.take(identifier_len)?)
+            .map_err(|_| DecodeError::BadIdentifier(ParseError::Malformed))?;
+        config::Identifier::parse(identifier).map_err(DecodeError::BadIdentifier)?;
+        let root_digest = D::Output::try_from(reader.take(digest_len as usize)?)
+            .map_err(|_| DecodeError::DigestLength(digest_len))?;
+
+        let root = reader.node::<D>(digest_len as usize, 0)?;
+        if !reader.bytes.is_empty() {
+            return Err(DecodeError::TrailingBytes);
+        }
+        if *root.digest() != root_digest {
+            return Err(DecodeError::DigestMismatch);
+        }
+
+        Ok(Self {
+            identifier: identifier.into(),
+            outline: Outline { root },
+        })
+    }
+}
+
+fn write_integer(out: &mut Vec<u8>, mut n: u64) {
+    while n >= 0x80 {
+        out.push(n as u8 | 0x80);
+        n >>= 7;
+    }
+    out.push(n as u8);
+}
+
+fn write_node<D: Digest>(out: &mut Vec<u8>, node: &OutlineNode<D>) {
+    match node {
+        OutlineNode::Internal { children, .. } => {
+            out.push(INTERNAL);
+            write_integer(out, children.len() as u64);
+            for child in children.iter() {
+                write_node(out, child);
+            }
+        }
+        OutlineNode::Leaf { chunks, .. } => {
+            out.push(LEAF);
+            write_integer(out, chunks.len() as u64);
+            for chunk in chunks.iter() {
+                out.extend_from_slice(chunk.digest.as_ref());
+                write_integer(out, chunk.len);
+                write_integer(out, chunk.level.map_or(0, |lev| lev as u64 + 1));
+            }
+        }
+    }
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, n: usize) -> Result<&'a [u8], DecodeError> {
+        if n > self.bytes.len() {
+            return Err(DecodeError::Truncated);
+        }
+        let (taken, rest) = self.bytes.split_at(n);
+        self.bytes = rest;
+
+        Ok(taken)
+    }
+
+    fn byte(&mut self) -> Result<u8, DecodeError> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn integer(&mut self) -> Result<u64, DecodeError> {
+        let mut n = 0u64;
+        for shift in (0..64).step_by(7) {
+            let byte = self.byte()?;
+            let bits = (byte & 0x7f) as u64;
+            if bits << shift >> shift != bits {
+                return Err(DecodeError::BadInteger);
+            }
+            n |= bits << shift;
+            if byte & 0x80 == 0 {
+                return Ok(n);
+            }
+        }
+
+        Err(DecodeError::BadInteger)
+    }
+
+    /// An integer that counts things still to come, each of which takes at
+    /// least one byte.
+    fn count(&mut self) -> Result<usize, DecodeError> {
+        let n = self.integer()?;
+        if n > self.bytes.len() as u64 {
+            return Err(DecodeError::Truncated);
+        }
+
+        Ok(n as usize)
+    }
+
+    fn node<D: Digest>(
+        &mut self,
+        digest_len: usize,
+        depth: usize,
+    ) -> Result<OutlineNode<D>, DecodeError>
+    where
+        D::Output: for<'b> TryFrom<&'b [u8]>,
+    {
+        if depth == MAX_DEPTH {
+            return Err(DecodeError::TooDeep);
+        }
+
+        let kind = self.byte()?;
+        if kind != LEAF && kind != INTERNAL {
+            return Err(DecodeError::BadNodeKind(kind));
+        }
+        let count = self.count()?;
+        // Only the root of an empty input can be an empty leaf.
+        if count == 0 && (kind == INTERNAL || depth > 0) {
+            return Err(DecodeError::EmptyNode);
+        }
+
+        if kind == INTERNAL {
+            let children = (0..count)
+                .map(|_| self.node::<D>(digest_len, depth + 1))
+                .collect::<Result<Box<[_]>, _>>()?;
+
+            return Ok(OutlineNode::Internal {
+                digest: digest::internal_digest::<D>(children.iter().map(OutlineNode::digest)),
+                len: checked_sum(children.iter().map(OutlineNode::len))?,
+                children,
+            });
+        }
+
+        let chunks = (0..count)
+            .map(|_| {
+                let digest = D::Output::try_from(self.take(digest_len)?)
+                    .map_err(|_| DecodeError::DigestLength(digest_len as u8))?;
+                let len = self.integer()?;
+                let level = match self.integer()? {
+                    0 => None,
+                    code => Some(u32::try_from(code - 1).map_err(|_| DecodeError::BadInteger)?),
+                };
+
+                Ok(ChunkRef { digest, len, level })
+            })
+            .collect::<Result<Box<[_]>, _>>()?;
+
+        Ok(OutlineNode::Leaf {
+            digest: digest::leaf_digest::<D>(chunks.iter().map(|chunk| &chunk.digest)),
+            len: checked_sum(chunks.iter().map(|chunk| chunk.len))?,
+            chunks,
+        })
+    }
+}
+
+fn checked_sum(mut lens: impl Iterator<Item = u64>) -> Result<u64, DecodeError> {
+    lens.try_fold(0u64, u64::checked_add)
+        .ok_or(DecodeError::BadInteger)
+}
+
+/// The ways in which an encoded manifest can fail to decode.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DecodeError {
+    /// The input does not begin with [`MAGIC`].
+    BadMagic,
+    /// The manifest was written in a version of the encoding other than
+    /// [`VERSION`].
+    UnsupportedVersion(u8),
+    /// The manifest was encoded with a different digest than the one it is
+    /// decoded with, as told by their [`Digest::NAME`]s.
+    WrongDigest,
+    /// The digests in the manifest are of a different length than those of
+    /// the digest it is decoded with.
+    DigestLength(u8),
+    /// The input ends in the middle of the manifest.
+    Truncated,
+    /// An integer is longer than ten bytes or does not fit where it is used.
+    BadInteger,
+    /// The identifier is not a configuration identifier. One that is not
+    /// valid UTF-8 is [`ParseError::Malformed`].
+    BadIdentifier(ParseError),
+    /// A node has a kind byte other than 0 or 1.
+    BadNodeKind(u8),
+    /// An internal node has no children, or a leaf other than the root has
+    /// no chunks.
+    EmptyNode,
+    /// The nodes are nested more deeply than [`MAX_DEPTH`].
+    TooDeep,
+    /// The digest of the root does not match the chunks.
+    DigestMismatch,
+    /// There are bytes after the end of the manifest.
+    TrailingBytes,
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        match self {
+            Self::BadMagic => f.write_str("not a manifest"),
+            Self::UnsupportedVersion(version) => {
+                write!(f, "unsupported manifest version {}", version)
+            }
+            Self::WrongDigest => f.write_str("manifest was encoded with another digest"),
+            Self::DigestLength(len) => write!(f, "unexpected digest length {} in manifest", len),
+            Self::Truncated => f.write_str("truncated manifest"),
+            Self::BadInteger => f.write_str("invalid integer in manifest"),
+            Self::BadIdentifier(e) => write!(f, "{} in manifest", e),
+            Self::BadNodeKind(kind) => write!(f, "invalid node kind {} in manifest", kind),
+            Self::EmptyNode => f.write_str("empty node in manifest"),
+            Self::TooDeep => f.write_str("nodes nested too deeply in manifest"),
+            Self::DigestMismatch => f.write_str("manifest does not match its digest"),
+            Self::TrailingBytes => f.write_str("trailing bytes after manifest"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+#[doc(cfg(feature = "std"))]
+impl std::error::Error for DecodeError {}
@@ -0,0 +1,185 @@
+//! Chunking a large slice on several threads with [`rayon`].
+//!
+//! The slice is cut into segments, and each segment is chunked on its own as
+//! if a chunk began where the segment does. The state of the hasher there is
+//! recovered by hashing the [`Ring::LEN`] bytes before the segment, which is
+//! why the hasher must be [`Memoryless`].
+//!
+//! The guesses are then checked in order. Starting from the true state at the
+//! beginning of a segment, the bytes are hashed again until a boundary falls
+//! where the guess also put one. From there on both have an empty chunk and
+//! the same state, so the rest of the guess is right and is taken as it is.
+
+use crate::chunk::ResumableChunk;
+use crate::config::Limits;
+use crate::iter::{Boundary, Cutter};
+#[allow(unused)]
+use crate::util::*;
+use crate::{Hasher, Leveled, Memoryless, Ring};
+
+use alloc::vec::Vec;
+use rayon::prelude::*;
+
+/// The number of bytes in each segment by default.
+pub const DEFAULT_SEGMENT_SIZE: usize = 1 << 22;
+
+/// Finds the same chunks as [`Spans`](crate::iter::Spans), chunking segments
+/// of `segment_size` bytes in parallel.
+///
+/// ```
+/// use hashsplit::algorithms::Cp32;
+/// use hashsplit::par::par_spans;
+/// use hashsplit::config::Fixed;
+///
+/// let data = [0; 10_000];
+/// let chunks = par_spans(Cp32, &data, Fixed::<8, 64, 1024>, 1 << 12);
+///
+/// assert_eq!(chunks.iter().map(|chunk| chunk.len()).sum::<usize>(), data.len());
+/// ```
+pub fn par_spans<Hash, Limit>(
+    hasher: Hash,
+    data: &[u8],
+    limits: Limit,
+    segment_size: usize,
+) -> Vec<ResumableChunk<'_, Hash>>
+where
+    Hash: Memoryless + Clone + Send + Sync,
+    Hash::Checksum: Leveled,
+    Hash::State: Clone + Send,
+    Limit: Limits + Clone + Send + Sync,
+{
+    assert!(segment_size > 0, "segments must not be empty");
+
+    let mut guesses: Vec<Guess<Hash, Limit>> = (0..data.len())
+        .step_by(segment_size)
+        .collect::<Vec<_>>()
+        .into_par_iter()
+        .map(|begin| {
+            let end = data.len().min(begin + segment_size);
+            Guess::make(hasher.clone(), limits.clone(), data, begin, end)
+        })
+        .collect();
+
+    let mut ends = Vec::new();
+    let (mut cutter, mut floor) = if guesses.is_empty() {
+        (Cutter::start(hasher, limits), 0)
+    } else {
+        // The first segment begins with the input, so its guess is right.
+        let first = guesses.remove(0);
+        ends = first.ends;
+        (first.cutter, first.floor)
+    };
+
+    for (index, guess) in guesses.into_iter().enumerate() {
+        let begin = (index + 1) * segment_size;
+        let end = data.len().min(begin + segment_size);
+        let mut guessed = guess.ends.into_iter().peekable();
+        let mut synced = false;
+
+        scan(
+            &mut cutter,
+            &mut floor,
+            data,
+            begin,
+            end,
+            |position, boundary| {
+                ends.push((position, boundary));
+                while guessed.next_if(|(other, _)| *other < position).is_some() {}
+                synced = guessed.next_if(|(other, _)| *other == position).is_some();
+                !synced
+            },
+        );
+
+        if synced {
+            ends.extend(guessed);
+            cutter = guess.cutter;
+            floor = guess.floor;
+        }
+    }
+
+    if let Some(ext) = cutter.finish() {
+        ends.push((data.len(), ext.boundary));
+    }
+
+    let mut begin = 0;
+    ends.into_iter()
+        .map(|(end, boundary)| {
+            let chunk = &data[core::mem::replace(&mut begin, end)..end];
+            ResumableChunk::at_boundary(chunk, boundary)
+        })
+        .collect()
+}
+
+/// The chunks of one segment, found as if a chunk began with it.
+struct Guess<Hash: Hasher, Limit> {
+    /// Where each boundary falls, with the boundary.
+    ends: Vec<(usize, Boundary<Hash>)>,
+    /// The cutter after the last byte of the segment.
+    cutter: Cutter<Hash, Limit>,
+    /// The first byte that may enter the window after the segment.
+    floor: usize,
+}
+
+impl<Hash: Memoryless, Limit: Limits> Guess<Hash, Limit>
+where
+    Hash::Checksum: Leveled,
+    Hash::State: Clone,
+{
+    fn make(hasher: Hash, limits: Limit, data: &[u8], begin: usize, end: usize) -> Self {
+        let mut cutter = Cutter::start(hasher, limits);
+        let mut floor = if cutter.resets() {
+            begin
+        } else {
+            let floor = begin.saturating_sub(<Hash::Ring as Ring>::LEN);
+            cutter.warm(&data[floor..begin]);
+
+            floor
+        };
+
+        let mut ends = Vec::new();
+        scan(
+            &mut cutter,
+            &mut floor,
+            data,
+            begin,
+            end,
+            |position, boundary| {
+                ends.push((position, boundary));
+                true
+            },
+        );
+
+        Self {
+            ends,
+            cutter,
+            floor,
+        }
+    }
+}
+
+/// Hashes `data[begin..end]`, passing each boundary to `found` along with the
+/// index just past it, until `found` returns `false`.
+///
+/// The window holds the bytes of `data` from `floor` on, and zeros before.
+fn scan<Hash: Hasher, Limit: Limits>(
+    cutter: &mut Cutter<Hash, Limit>,
+    floor: &mut usize,
+    data: &[u8],
+    begin: usize,
+    end: usize,
+    mut found: impl FnMut(usize, Boundary<Hash>) -> bool,
+) where
+    Hash::Checksum: Leveled,
+    Hash::State: Clone,
+{
+    for position in begin..end {
+        if let Some(ext) = cutter.step_at(data, *floor, position) {
+            if cutter.resets() {
+                *floor = position + 1;
+            }
+            if !found(position + 1, ext.boundary) {
+                return;
+            }
+        }
+    }
+}
@@ -0,0 +1,144 @@
+//! Chunking an edited input again, without going over the parts the edit
+//! cannot have changed.
+//!
+//! A boundary depends only on the bytes before it, so the chunks that end
+//! before an edit stay as they were. Chunking resumes after the last of them
+//! and goes on until it finds a boundary where the old chunks had one, past
+//! the edit by at least a window. From there the window is the same as
+//! before, and with it the state, since the hasher is [`Memoryless`], so the
+//! old chunks that follow are right again.
+
+use crate::config::Limits;
+use crate::iter::{Boundary, Distances, Extend};
+#[allow(unused)]
+use crate::util::*;
+use crate::{Hasher, Leveled, Memoryless, Ring};
+
+use alloc::vec::Vec;
+use core::ops::Range;
+
+/// The new chunks for an edited input, and the old chunks they replace.
+pub struct Rechunk<Hash: Hasher> {
+    /// The indices of the old chunks that were replaced. The ones before and
+    /// after are unchanged, apart from where the latter begin.
+    pub replaced: Range<usize>,
+    /// Where the new chunks begin in the edited input.
+    pub offset: usize,
+    /// The new chunks, in order.
+    pub chunks: Vec<Extend<Hash>>,
+}
+
+/// Finds the chunks of `data` that differ from `old`, the chunks of the input
+/// before it was edited.
+///
+/// `edited` is the range of old bytes that were removed, and `data` holds the
+/// edited input, in which the bytes inserted in their place begin at
+/// `edited.start`.
+///
+/// ```
+/// use hashsplit::algorithms::Cp32;
+/// use hashsplit::Config;
+///
+/// let cfg: Config<Cp32, 8, 64, 1024> = Default::default();
+/// let mut data = vec![0; 10_000];
+/// let mut chunks: Vec<_> = cfg.distances(data.iter().copied()).collect();
+///
+/// data.splice(5000..5010, b"hello, world!".iter().copied());
+/// let rechunk = cfg.rechunk(&chunks, 5000..5010, &data);
+/// chunks.splice(rechunk.replaced, rechunk.chunks);
+///
+/// assert_eq!(chunks.iter().map(|ext| ext.length.get()).sum::<usize>(), data.len());
+/// ```
+pub fn rechunk<Hash: Memoryless, Limit: Limits>(
+    hasher: Hash,
+    limits: Limit,
+    old: &[Extend<Hash>],
+    edited: Range<usize>,
+    data: &[u8],
+) -> Rechunk<Hash>
+where
+    Hash::Checksum: Leveled,
+    Hash::State: Clone,
+{
+    let old_len: usize = old.iter().map(|ext| ext.length.get()).sum();
+    assert!(
+        edited.start <= edited.end && edited.end <= old_len,
+        "the edit must lie within the old input"
+    );
+    let kept = old_len - edited.len();
+    assert!(
+        data.len() >= kept,
+        "the edited input must keep the bytes outside the edit"
+    );
+    // The end of the inserted bytes in `data`.
+    let inserted_end = edited.start + (data.len() - kept);
+
+    // Resume after the last real boundary that comes before the edit.
+    let mut first = 0;
+    let mut offset = 0;
+    for ext in old {
+        let end = offset + ext.length.get();
+        if end > edited.start || matches!(ext.boundary, Boundary::Eof(_)) {
+            break;
+        }
+        first += 1;
+        offset = end;
+    }
+
+    let resets = hasher.resets_at_boundary();
+    let source = data[offset..].iter().copied();
+    let distances = if first == 0 {
+        Distances::start(hasher, source, limits)
+    } else {
+        let state = old[first - 1].boundary.state().clone();
+        Distances::resume(hasher, source, limits, state, &data[..offset])
+    };
+
+    // Where an old boundary past the edit falls in `data`.
+    let moved = |end: usize| (end >= edited.end).then(|| end - edited.end + inserted_end);
+    // The first boundary whose window holds none of the inserted bytes.
+    let clean = if resets {
+        inserted_end
+    } else {
+        inserted_end + <Hash::Ring as Ring>::LEN
+    };
+
+    let mut chunks = Vec::new();
+    let mut position = offset;
+    let mut old_index = first;
+    let mut old_end = offset;
+    for ext in distances {
+        position += ext.length.get();
+        let eof = matches!(ext.boundary, Boundary::Eof(_));
+        chunks.push(ext);
+        if eof || position < clean {
+            continue;
+        }
+
+        while let Some(next) = old.get(old_index) {
+            let end = old_end + next.length.get();
+            match moved(end) {
+                Some(moved_end) if moved_end >= position => {
+                    if moved_end == position && !matches!(next.boundary, Boundary::Eof(_)) {
+                        return Rechunk {
+                            replaced: first..old_index + 1,
+                            offset,
+                            chunks,
+                        };
+                    }
+                    break;
+                }
+                _ => {
+                    old_index += 1;
+                    old_end = end;
+                }
+            }
+        }
+    }
+
+    Rechunk {
+        replaced: first..old.len(),
+        offset,
+        chunks,
+    }
+}
@@ -0,0 +1,479 @@
+//! Places to keep chunks, looked up by their digests.
+//!
+//! Chunks with the same contents have the same digest, so a store keeps each
+//! one only once and reports the others as duplicates.
+//!
+//! [`MemoryStore`] needs nothing beyond the `alloc` feature, which `store`
+//! turns on. `DirStore`, which keeps chunks in a directory, and `TreeReader`,
+//! which reads an input back from a store, also need the `std` feature.
+//!
+//! ```
+//! # #[cfg(feature = "blake3")]
+//! # fn main() {
+//! use hashsplit::algorithms::Cp32;
+//! use hashsplit::digest::Blake3;
+//! use hashsplit::store::{ChunkStore, MemoryStore};
+//! use hashsplit::Config;
+//!
+//! let cfg: Config<Cp32, 8, 64, 1024> = Default::default();
+//! let data = [0; 10_000];
+//! let mut store = MemoryStore::<Blake3>::new();
+//!
+//! let put = store.put_chunks(cfg.spans(&data)).unwrap();
+//! assert_eq!(store.len() + put.duplicates, put.digests.len());
+//! # }
+//! # #[cfg(not(feature = "blake3"))]
+//! # fn main() {}
+//! ```
+
+use crate::digest::{chunk_digest, Digest};
+#[allow(unused)]
+use crate::util::*;
+
+use alloc::borrow::Cow;
+use alloc::boxed::Box;
+use alloc::collections::BTreeMap;
+use alloc::vec::Vec;
+use core::convert::Infallible;
+use core::ops::Deref;
+
+/// The result of putting a chunk into a [`ChunkStore`].
+pub struct Put<D: Digest> {
+    /// The digest of the chunk, under which the store keeps it.
+    pub digest: D::Output,
+    /// Whether the store had the chunk already.
+    pub duplicate: bool,
+}
+
+/// The result of putting a sequence of chunks into a [`ChunkStore`].
+pub struct Report<D: Digest> {
+    /// The digests of the chunks, in order.
+    pub digests: Vec<D::Output>,
+    /// How many of the chunks the store had already.
+    pub duplicates: usize,
+    /// The total length of those chunks.
+    pub duplicate_bytes: u64,
+}
+
+/// A place to keep chunks, looked up by their digests under `D`.
+pub trait ChunkStore<D: Digest> {
+    type Error;
+
+    /// Keeps `chunk` under `digest`, which must be its [`chunk_digest`],
+    /// unless there is a chunk with that digest already. Returns whether there
+    /// was.
+    fn insert(&mut self, digest: &D::Output, chunk: &[u8]) -> Result<bool, Self::Error>;
+
+    /// The chunk with the given digest, if the store has it.
+    fn get(&self, digest: &D::Output) -> Result<Option<Cow<'_, [u8]>>, Self::Error>;
+
+    /// Whether the store has the chunk with the given digest, without reading
+    /// it.
+    fn has(&self, digest: &D::Output) -> Result<bool, Self::Error>;
+
+    /// Keeps `chunk`, returning its [`chunk_digest`].
+    fn put(&mut self, chunk: &[u8]) -> Result<Put<D>, Self::Error> {
+        let digest = chunk_digest::<D>(chunk);
+        let duplicate = self.insert(&digest, chunk)?;
+
+        Ok(Put { digest, duplicate })
+    }
+
+    /// Keeps every chunk from an iterator such as
+    /// [`Spans`](crate::iter::Spans) or [`Splits`](crate::iter::Splits).
+    fn put_chunks<I>(&mut self, chunks: I) -> Result<Report<D>, Self::Error>
+    where
+        I: IntoIterator,
+        I::Item: Deref<Target = [u8]>,
+    {
+        let mut report = Report {
+            digests: Vec::new(),
+            duplicates: 0,
+            duplicate_bytes: 0,
+        };
+
+        for chunk in chunks {
+            let put = self.put(&chunk)?;
+            if put.duplicate {
+                report.duplicates += 1;
+                report.duplicate_bytes += chunk.len() as u64;
+            }
+            report.digests.push(put.digest);
+        }
+
+        Ok(report)
+    }
+}
+
+/// A [`ChunkStore`] that keeps chunks in memory.
+pub struct MemoryStore<D: Digest> {
+    chunks: BTreeMap<D::Output, Box<[u8]>>,
+}
+
+impl<D: Digest> MemoryStore<D> {
+    pub fn new() -> Self {
+        Self {
+            chunks: BTreeMap::new(),
+        }
+    }
+
+    /// The number of distinct chunks in the store.
+    pub fn len(&self) -> usize {
+        self.chunks.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.chunks.is_empty()
+    }
+}
+
+impl<D: Digest> Default for MemoryStore<D> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<D: Digest> ChunkStore<D> for MemoryStore<D> {
+    type Error = Infallible;
+
+    fn insert(&mut self, digest: &D::Output, chunk: &[u8]) -> Result<bool, Self::Error> {
+        if self.chunks.contains_key(digest) {
+            return Ok(true);
+        }
+        self.chunks.insert(digest.clone(), chunk.into());
+
+        Ok(false)
+    }
+
+    fn get(&self, digest: &D::Output) -> Result<Option<Cow<'_, [u8]>>, Self::Error> {
+        Ok(self.chunks.get(digest).map(|chunk| Cow::Borrowed(&**chunk)))
+    }
+
+    fn has(&self, digest: &D::Output) -> Result<bool, Self::Error> {
+        Ok(self.chunks.contains_key(digest))
+    }
+}
+
+#[cfg(feature = "std")]
+pub use self::dir::DirStore;
+#[cfg(feature = "std")]
+pub use self::reader::TreeReader;
+
+#[cfg(feature = "std")]
+mod dir {
+    use super::ChunkStore;
+    use crate::digest::Digest;
+
+    use core::marker::PhantomData;
+    use core::sync::atomic::{AtomicU64, Ordering};
+    use std::borrow::Cow;
+    use std::format;
+    use std::fs;
+    use std::io::{self, Write};
+    use std::path::{Path, PathBuf};
+    use std::process;
+    use std::string::String;
+
+    /// Tells apart the temporary files of the inserts in this process.
+    static PARTIAL: AtomicU64 = AtomicU64::new(0);
+
+    /// A [`ChunkStore`] that keeps each chunk in a file of its own, named
+    /// after its digest in hexadecimal, in one directory.
+    ///
+    /// A chunk is written to a temporary file first, synced to disk, and then
+    /// renamed, so the files that have digests for names are always complete,
+    /// even after a crash. Each insert has a temporary file of its own, so
+    /// several stores, in one process or more, can share a directory.
+    #[doc(cfg(feature = "std"))]
+    pub struct DirStore<D> {
+        root: PathBuf,
+        _digest: PhantomData<fn() -> D>,
+    }
+
+    impl<D: Digest> DirStore<D> {
+        /// Uses the directory at `root`, creating it if need be.
+        pub fn open<P: Into<PathBuf>>(root: P) -> io::Result<Self> {
+            let root = root.into();
+            fs::create_dir_all(&root)?;
+
+            Ok(Self {
+                root,
+                _digest: PhantomData,
+            })
+        }
+
+        /// The file that holds the chunk with the given digest.
+        pub fn path(&self, digest: &D::Output) -> PathBuf {
+            self.root.join(hex(digest.as_ref()))
+        }
+    }
+
+    impl<D: Digest> ChunkStore<D> for DirStore<D> {
+        type Error = io::Error;
+
+        fn insert(&mut self, digest: &D::Output, chunk: &[u8]) -> io::Result<bool> {
+            let path = self.path(digest);
+            if path.exists() {
+                return Ok(true);
+            }
+
+            let partial = path.with_extension(format!(
+                "{}-{}.partial",
+                process::id(),
+                PARTIAL.fetch_add(1, Ordering::Relaxed)
+            ));
+            let written = write_synced(&partial, chunk).and_then(|()| fs::rename(&partial, &path));
+            if let Err(e) = written {
+                let _ = fs::remove_file(&partial);
+                return Err(e);
+            }
+            sync_dir(&self.root)?;
+
+            Ok(false)
+        }
+
+        fn get(&self, digest: &D::Output) -> io::Result<Option<Cow<'_, [u8]>>> {
+            match fs::read(self.path(digest)) {
+                Ok(chunk) => Ok(Some(Cow::Owned(chunk))),
+                Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+                Err(e) => Err(e),
+            }
+        }
+
+        fn has(&self, digest: &D::Output) -> io::Result<bool> {
+            Ok(self.path(digest).exists())
+        }
+    }
+
+    fn write_synced(path: &Path, contents: &[u8]) -> io::Result<()> {
+        let mut file = fs::OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(path)?;
+        file.write_all(contents)?;
+
+        file.sync_all()
+    }
+
+    /// Makes a rename in the directory at `path` durable.
+    #[cfg(unix)]
+    fn sync_dir(path: &Path) -> io::Result<()> {
+        fs::File::open(path)?.sync_all()
+    }
+
+    /// Directories cannot be opened, let alone synced, on other systems.
+    #[cfg(not(unix))]
+    fn sync_dir(_path: &Path) -> io::Result<()> {
+        Ok(())
+    }
+
+    fn hex(bytes: &[u8]) -> String {
+        const DIGITS: &[u8; 16] = b"0123456789abcdef";
+
+        bytes
+            .iter()
+            .flat_map(|&byte| [byte >> 4, byte & 0xf])
+            .map(|nibble| DIGITS[nibble as usize] as char)
+            .collect()
+    }
+}
+
+#[cfg(feature = "std")]
+mod reader {
+    use super::ChunkStore;
+    use crate::chunk::{ChunkRef, Outline, OutlineNode, Tree};
+    use crate::digest::{chunk_digest, Digest};
+    use crate::Hasher;
+
+    use std::borrow::Cow;
+    use std::boxed::Box;
+    use std::error::Error;
+    use std::io::{self, Read, Seek, SeekFrom};
+
+    /// Puts an input back together from the chunks in a [`ChunkStore`],
+    /// following an [`Outline`], such as the one [`Tree::outline`] returns.
+    ///
+    /// Seeking walks down the outline using the length of each node, so only
+    /// the chunks that are read are fetched. Every chunk is checked against
+    /// its digest and length when it is fetched, and the outline itself when
+    /// the reader is made; a mismatch is reported as
+    /// [`InvalidData`](io::ErrorKind::InvalidData), and a chunk missing from
+    /// the store as [`NotFound`](io::ErrorKind::NotFound).
+    ///
+    /// ```
+    /// # #[cfg(feature = "blake3")]
+    /// # fn main() {
+    /// use hashsplit::algorithms::Cp32;
+    /// use hashsplit::chunk::TreeBuilder;
+    /// use hashsplit::digest::Blake3;
+    /// use hashsplit::store::{ChunkStore, MemoryStore, TreeReader};
+    /// use hashsplit::Config;
+    /// use std::io::{Read, Seek, SeekFrom};
+    ///
+    /// let cfg: Config<Cp32, 8, 64, 1024> = Default::default();
+    /// let data: Vec<u8> = (0..10_000).map(|i| i as u8).collect();
+    ///
+    /// let mut store = MemoryStore::<Blake3>::new();
+    /// store.put_chunks(cfg.spans(&data)).unwrap();
+    /// let mut builder = TreeBuilder::<_, Blake3>::new(8);
+    /// builder.extend(cfg.spans(&data));
+    /// let tree = builder.finish().unwrap();
+    ///
+    /// let mut reader = TreeReader::from_tree(&tree, &store).unwrap();
+    /// let mut middle = [0; 100];
+    /// reader.seek(SeekFrom::Start(5000)).unwrap();
+    /// reader.read_exact(&mut middle).unwrap();
+    /// assert_eq!(&middle[..], &data[5000..5100]);
+    /// # }
+    /// # #[cfg(not(feature = "blake3"))]
+    /// # fn main() {}
+    /// ```
+    #[doc(cfg(feature = "std"))]
+    pub struct TreeReader<'s, D: Digest, S> {
+        outline: Outline<D>,
+        store: &'s S,
+        position: u64,
+        // The chunk that was read last, and where it begins.
+        current: Option<(u64, Cow<'s, [u8]>)>,
+    }
+
+    impl<'s, D: Digest, S: ChunkStore<D>> TreeReader<'s, D, S>
+    where
+        S::Error: Into<Box<dyn Error + Send + Sync>>,
+    {
+        pub fn new(outline: Outline<D>, store: &'s S) -> io::Result<Self> {
+            if !outline.root.verify() {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "the outline does not match its digests",
+                ));
+            }
+
+            Ok(Self {
+                outline,
+                store,
+                position: 0,
+                current: None,
+            })
+        }
+
+        /// Reads the input of `tree`, like `new(tree.outline(), store)`.
+        pub fn from_tree<Hash: Hasher>(tree: &Tree<'_, Hash, D>, store: &'s S) -> io::Result<Self> {
+            Self::new(tree.outline(), store)
+        }
+
+        pub fn outline(&self) -> &Outline<D> {
+            &self.outline
+        }
+
+        /// Fetches the chunk that holds the byte at `self.position`, which
+        /// must be within the input.
+        fn fetch(&mut self) -> io::Result<()> {
+            let (begin, chunk) = find(&self.outline.root, self.position);
+            let data = self
+                .store
+                .get(&chunk.digest)
+                .map_err(to_io_error)?
+                .ok_or_else(|| {
+                    io::Error::new(io::ErrorKind::NotFound, "a chunk is missing from the store")
+                })?;
+            if data.len() as u64 != chunk.len || chunk_digest::<D>(&data) != chunk.digest {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "a chunk does not match its digest",
+                ));
+            }
+
+            self.current = Some((begin, data));
+
+            Ok(())
+        }
+    }
+
+    /// The chunk below `node` that holds the byte at `offset`, and where it
+    /// begins.
+    fn find<D: Digest>(mut node: &OutlineNode<D>, mut offset: u64) -> (u64, &ChunkRef<D>) {
+        let mut begin = 0;
+        loop {
+            match node {
+                OutlineNode::Internal { children, .. } => {
+                    for child in children.iter() {
+                        node = child;
+                        if offset < child.len() {
+                            break;
+                        }
+                        offset -= child.len();
+                        begin += child.len();
+                    }
+                }
+                OutlineNode::Leaf { chunks, .. } => {
+                    for chunk in chunks.iter() {
+                        if offset < chunk.len {
+                            return (begin, chunk);
+                        }
+                        offset -= chunk.len;
+                        begin += chunk.len;
+                    }
+                    unreachable!("the offset must be within the node");
+                }
+            }
+        }
+    }
+
+    fn to_io_error<E: Into<Box<dyn Error + Send + Sync>>>(e: E) -> io::Error {
+        match e.into().downcast::<io::Error>() {
+            Ok(e) => *e,
+            Err(e) => io::Error::other(e),
+        }
+    }
+
+    impl<'s, D: Digest, S: ChunkStore<D>> Read for TreeReader<'s, D, S>
+    where
+        S::Error: Into<Box<dyn Error + Send + Sync>>,
+    {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            if buf.is_empty() || self.position >= self.outline.len() {
+                return Ok(0);
+            }
+
+            let within = |(begin, data): &(u64, Cow<'s, [u8]>)| {
+                (*begin..*begin + data.len() as u64).contains(&self.position)
+            };
+            if !self.current.as_ref().is_some_and(within) {
+                self.fetch()?;
+            }
+
+            let (begin, data) = self.current.as_ref().expect("a chunk was just fetched");
+            let available = &data[(self.position - begin) as usize..];
+            let n = available.len().min(buf.len());
+            buf[..n].copy_from_slice(&available[..n]);
+            self.position += n as u64;
+
+            Ok(n)
+        }
+    }
+
+    impl<'s, D: Digest, S> Seek for TreeReader<'s, D, S> {
+        fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+            let (base, offset) = match pos {
+                SeekFrom::Start(offset) => {
+                    self.position = offset;
+                    return Ok(offset);
+                }
+                SeekFrom::End(offset) => (self.outline.len(), offset),
+                SeekFrom::Current(offset) => (self.position, offset),
+            };
+
+            match base.checked_add_signed(offset) {
+                Some(position) => {
+                    self.position = position;
+                    Ok(position)
+                }
+                None => Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    "invalid seek to a negative or overflowing position",
+                )),
+            }
+        }
+    }
+}
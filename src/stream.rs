@@ -0,0 +1,193 @@
+use crate::chunk::ResumableChunk;
+use crate::config::Limits;
+use crate::io::{Assembler, DEFAULT_BUFFER_SIZE};
+#[allow(unused)]
+use crate::util::*;
+use crate::{Hasher, Leveled};
+
+use core::pin::Pin;
+use core::task::{Context, Poll};
+use futures_core::Stream;
+use futures_io::AsyncRead;
+use std::io;
+use std::vec::Vec;
+
+/// Chunks the bytes of an [`AsyncRead`], yielding the same chunks as
+/// [`ReadChunks`](crate::io::ReadChunks).
+///
+/// ```
+/// use futures::executor::block_on;
+/// use futures::io::Cursor;
+/// use futures::TryStreamExt;
+/// use hashsplit::algorithms::Cp32;
+/// use hashsplit::Config;
+///
+/// let cfg: Config<Cp32, 8, 64, 1024> = Default::default();
+/// let input = [0; 10_000];
+///
+/// let chunks: Vec<_> = block_on(cfg.async_read_chunks(Cursor::new(&input)).try_collect()).unwrap();
+///
+/// assert_eq!(chunks.iter().map(|chunk| chunk.len()).sum::<usize>(), input.len());
+/// ```
+pub struct AsyncReadChunks<Hash: Hasher, Reader, Limit> {
+    assembler: Assembler<Hash, Limit>,
+    buffer: Vec<u8>,
+    begin: usize,
+    end: usize,
+    halt: bool,
+    pub reader: Reader,
+}
+
+impl<Hash: Hasher, Reader: AsyncRead + Unpin, Limit: Limits> AsyncReadChunks<Hash, Reader, Limit>
+where
+    Hash::Checksum: Leveled,
+    Hash::State: Clone,
+{
+    pub fn start(hasher: Hash, reader: Reader, limits: Limit) -> Self {
+        Self::with_buffer_size(hasher, reader, limits, DEFAULT_BUFFER_SIZE)
+    }
+
+    pub fn with_buffer_size(
+        hasher: Hash,
+        reader: Reader,
+        limits: Limit,
+        buffer_size: usize,
+    ) -> Self {
+        assert!(buffer_size > 0, "the buffer must not be empty");
+
+        Self {
+            assembler: Assembler::start(hasher, limits),
+            buffer: std::vec![0; buffer_size],
+            begin: 0,
+            end: 0,
+            halt: false,
+            reader,
+        }
+    }
+}
+
+// Only the reader is ever polled, and it is required to be `Unpin`.
+impl<Hash: Hasher, Reader: Unpin, Limit> Unpin for AsyncReadChunks<Hash, Reader, Limit> {}
+
+impl<Hash: Hasher, Reader: AsyncRead + Unpin, Limit: Limits> Stream
+    for AsyncReadChunks<Hash, Reader, Limit>
+where
+    Hash::Checksum: Leveled,
+    Hash::State: Clone,
+{
+    type Item = io::Result<ResumableChunk<'static, Hash>>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+
+        while !this.halt {
+            if this.begin == this.end {
+                match Pin::new(&mut this.reader).poll_read(cx, &mut this.buffer) {
+                    Poll::Pending => return Poll::Pending,
+                    Poll::Ready(Ok(0)) => {
+                        this.halt = true;
+                        break;
+                    }
+                    Poll::Ready(Ok(n)) => {
+                        this.begin = 0;
+                        this.end = n;
+                    }
+                    Poll::Ready(Err(e)) if e.kind() == io::ErrorKind::Interrupted => continue,
+                    Poll::Ready(Err(e)) => return Poll::Ready(Some(Err(e))),
+                }
+            }
+
+            let (consumed, chunk) = this.assembler.push(&this.buffer[this.begin..this.end]);
+            this.begin += consumed;
+
+            if let Some(chunk) = chunk {
+                return Poll::Ready(Some(Ok(chunk)));
+            }
+        }
+
+        Poll::Ready(this.assembler.finish().map(Ok))
+    }
+}
+
+/// Chunks the bytes of a [`Stream`] of buffers, such as `Bytes`, passing any
+/// error of the stream through.
+///
+/// ```
+/// use futures::executor::block_on;
+/// use futures::stream::{self, TryStreamExt};
+/// use hashsplit::algorithms::Cp32;
+/// use hashsplit::Config;
+///
+/// let cfg: Config<Cp32, 8, 64, 1024> = Default::default();
+/// let pieces = stream::iter([Ok::<_, ()>(vec![0; 6000]), Ok(vec![1; 4000])]);
+///
+/// let chunks: Vec<_> = block_on(cfg.stream_chunks(pieces).try_collect()).unwrap();
+///
+/// assert_eq!(chunks.iter().map(|chunk| chunk.len()).sum::<usize>(), 10_000);
+/// ```
+pub struct StreamChunks<Hash: Hasher, Source, Buffer, Limit> {
+    assembler: Assembler<Hash, Limit>,
+    current: Option<(Buffer, usize)>,
+    halt: bool,
+    pub source: Source,
+}
+
+impl<Hash: Hasher, Source, Buffer, Limit: Limits> StreamChunks<Hash, Source, Buffer, Limit>
+where
+    Hash::Checksum: Leveled,
+    Hash::State: Clone,
+{
+    pub fn start(hasher: Hash, source: Source, limits: Limit) -> Self {
+        Self {
+            assembler: Assembler::start(hasher, limits),
+            current: None,
+            halt: false,
+            source,
+        }
+    }
+}
+
+// Only the source is ever polled, and it is required to be `Unpin`.
+impl<Hash: Hasher, Source: Unpin, Buffer, Limit> Unpin
+    for StreamChunks<Hash, Source, Buffer, Limit>
+{
+}
+
+impl<Hash: Hasher, Source, Buffer, Error, Limit> Stream
+    for StreamChunks<Hash, Source, Buffer, Limit>
+where
+    Hash::Checksum: Leveled,
+    Hash::State: Clone,
+    Source: Stream<Item = Result<Buffer, Error>> + Unpin,
+    Buffer: AsRef<[u8]>,
+    Limit: Limits,
+{
+    type Item = Result<ResumableChunk<'static, Hash>, Error>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+
+        while !this.halt {
+            if let Some((buffer, begin)) = &mut this.current {
+                let (consumed, chunk) = this.assembler.push(&buffer.as_ref()[*begin..]);
+                *begin += consumed;
+                if *begin == buffer.as_ref().len() {
+                    this.current = None;
+                }
+
+                if let Some(chunk) = chunk {
+                    return Poll::Ready(Some(Ok(chunk)));
+                }
+            }
+
+            match Pin::new(&mut this.source).poll_next(cx) {
+                Poll::Pending => return Poll::Pending,
+                Poll::Ready(None) => this.halt = true,
+                Poll::Ready(Some(Ok(buffer))) => this.current = Some((buffer, 0)),
+                Poll::Ready(Some(Err(e))) => return Poll::Ready(Some(Err(e))),
+            }
+        }
+
+        Poll::Ready(this.assembler.finish().map(Ok))
+    }
+}
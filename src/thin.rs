@@ -2,6 +2,12 @@
 use crate::util::*;
 use crate::Hasher;
 
+/// A [`Hasher`] that can process a block of bytes at once, faster than one
+/// byte at a time.
+///
+/// The result must be the same as that of
+/// [`process_sequence`](Hasher::process_sequence) on the pairs of old and new
+/// bytes; only the checksum after the last byte is returned.
 pub trait Thinned<Block: AsRef<[u8]>>: Hasher {
     const BLOCK_SIZE: usize;
 
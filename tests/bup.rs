@@ -0,0 +1,91 @@
+//! Checks `BupRollsum` against bup.
+//!
+//! The expected values were printed by `bupsplit.c` from bup, compiled with
+//! a `main` that fills a buffer the way its `bupsplit_selftest` does, from
+//! glibc's `random` seeded with 1, and splits it as `_splitbuf` in
+//! `hashsplit.py` does.
+
+#![cfg(feature = "alloc")]
+
+use hashsplit::algorithms::{bup, BupRollsum};
+use hashsplit::iter::Boundary;
+use hashsplit::{Config, Hasher};
+
+/// The low bytes of the first `len` outputs of glibc's `random` after
+/// `srandom(seed)`.
+fn glibc_random(seed: u32, len: usize) -> Vec<u8> {
+    let mut r = vec![seed as i32];
+    for i in 1..31 {
+        let (hi, lo) = (r[i - 1] / 127_773, r[i - 1] % 127_773);
+        let word = 16_807 * lo - 2_836 * hi;
+        r.push(if word < 0 { word + 2_147_483_647 } else { word });
+    }
+    for i in 31..34 {
+        r.push(r[i - 31]);
+    }
+    for i in 34..344 + len {
+        r.push(r[i - 31].wrapping_add(r[i - 3]));
+    }
+
+    r[344..]
+        .iter()
+        .map(|&word| (word as u32 >> 1) as u8)
+        .collect()
+}
+
+/// bup's `rollsum_sum`: the digest after rolling in `buf[ofs..len]`.
+fn rollsum_sum(buf: &[u8], ofs: usize, len: usize) -> u32 {
+    let mut window = [0; 64];
+    let mut state = BupRollsum::INITIAL_STATE;
+    for (i, &byte) in buf[ofs..len].iter().enumerate() {
+        state = bup::process_byte_freestanding(state, window[i % 64], byte).1;
+        window[i % 64] = byte;
+    }
+    let (s1, s2) = state;
+
+    s1 << 16 | s2 & 0xffff
+}
+
+#[test]
+fn selftest() {
+    let buf = glibc_random(1, 100_000);
+    assert_eq!(buf[..4], [0x67, 0xc6, 0x69, 0x73]);
+
+    assert_eq!(rollsum_sum(&buf, 0, 100_000), 0x2b38_9d40);
+    assert_eq!(rollsum_sum(&buf, 1, 100_000), 0x2b38_9d40);
+    assert_eq!(rollsum_sum(&buf, 100_000 - 160, 100_000 - 64), 0x253a_965b);
+    assert_eq!(rollsum_sum(&buf, 0, 100_000 - 64), 0x253a_965b);
+    assert_eq!(rollsum_sum(&buf, 0, 67), 0x27f5_45a2);
+    assert_eq!(rollsum_sum(&buf, 3, 67), 0x27f5_45a2);
+    assert_eq!(rollsum_sum(&buf, 0, 10), 0x0d45_0595);
+}
+
+#[test]
+fn splits() {
+    let buf = glibc_random(1, 100_000);
+    let cfg: Config<BupRollsum, 13, 0, 0x80_00> = Default::default();
+
+    let mut end = 0;
+    let mut found = Vec::new();
+    for ext in cfg.distances(buf.iter().copied()) {
+        end += ext.length.get();
+        let level = match ext.boundary {
+            Boundary::Level(lev, _) => Some(lev),
+            _ => None,
+        };
+        found.push((end, level));
+    }
+
+    assert_eq!(
+        found,
+        [
+            (21_052, Some(13)),
+            (38_484, Some(13)),
+            (41_657, Some(13)),
+            (51_224, Some(14)),
+            (63_871, Some(13)),
+            (93_089, Some(13)),
+            (100_000, None),
+        ]
+    );
+}
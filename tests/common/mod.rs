@@ -0,0 +1,151 @@
+//! Helpers shared by the tests.
+
+// Not every test uses every helper.
+#![allow(dead_code)]
+
+use hashsplit::digest::Digest;
+use hashsplit::iter::{Boundary, Event, Extend};
+use hashsplit::Hasher;
+use proptest::prelude::*;
+
+/// FNV-1a, which is plenty to tell apart the chunks of these tests.
+#[derive(Default)]
+pub struct Fnv(Option<u64>);
+
+impl Digest for Fnv {
+    type Output = [u8; 8];
+
+    const NAME: &'static str = "fnv1a64";
+
+    fn update(&mut self, data: &[u8]) {
+        let hash = data
+            .iter()
+            .fold(self.0.unwrap_or(0xcbf2_9ce4_8422_2325), |hash, &byte| {
+                (hash ^ byte as u64).wrapping_mul(0x100_0000_01b3)
+            });
+        self.0 = Some(hash);
+    }
+
+    fn finalize(self) -> Self::Output {
+        self.0.unwrap_or(0xcbf2_9ce4_8422_2325).to_le_bytes()
+    }
+}
+
+/// Bytes from SplitMix64, which has no repeats that would make duplicates.
+pub fn data() -> Vec<u8> {
+    let mut state = 0u64;
+    (0..1 << 16)
+        .map(|_| {
+            state = state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+            let mut z = state;
+            z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+            z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+            (z ^ (z >> 31)) as u8
+        })
+        .collect()
+}
+
+/// A threshold below `thresholds`, a minimum size below `min_sizes`, and a
+/// maximum size that is either 0, for none, or at most `spread` above the
+/// minimum.
+pub fn limits(
+    thresholds: u32,
+    min_sizes: usize,
+    spread: usize,
+) -> impl Strategy<Value = (u32, usize, usize)> {
+    (0..thresholds, 0..min_sizes).prop_flat_map(move |(threshold, min)| {
+        (
+            Just(threshold),
+            Just(min),
+            prop_oneof![Just(0usize), min.max(1)..min + spread],
+        )
+    })
+}
+
+/// Inputs of fewer than `len` bytes. Those drawn from a small alphabet hit
+/// runs and repeats more often, and long runs of one byte keep boundaries
+/// from lining up for a while.
+pub fn inputs(len: usize) -> impl Strategy<Value = Vec<u8>> {
+    prop_oneof![
+        proptest::collection::vec(any::<u8>(), 0..len),
+        proptest::collection::vec(0u8..4, 0..len),
+        proptest::collection::vec(prop_oneof![Just(0u8), any::<u8>()], 0..len),
+    ]
+}
+
+/// What ended a chunk, with the state there.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Mark<State> {
+    Level(u32, State),
+    Capped(State),
+    Eof(State),
+}
+
+impl<State> Mark<State> {
+    pub fn of<Hash: Hasher<State = State>>(boundary: Boundary<Hash>) -> Self {
+        match boundary {
+            Boundary::Level(lev, state) => Self::Level(lev, state),
+            Boundary::Capped(state) => Self::Capped(state),
+            Boundary::Eof(state) => Self::Eof(state),
+        }
+    }
+
+    pub fn state(&self) -> &State {
+        match self {
+            Self::Level(_, state) | Self::Capped(state) | Self::Eof(state) => state,
+        }
+    }
+
+    /// The same mark without the state.
+    pub fn forget(self) -> Mark<()> {
+        match self {
+            Self::Level(lev, _) => Mark::Level(lev, ()),
+            Self::Capped(_) => Mark::Capped(()),
+            Self::Eof(_) => Mark::Eof(()),
+        }
+    }
+}
+
+/// The end offset of every chunk, with what ended it.
+pub type Boundaries<State> = Vec<(usize, Mark<State>)>;
+
+/// The boundaries of the chunks that the events of a `Delimited` delimit.
+pub fn delimited<Hash: Hasher>(
+    events: impl Iterator<Item = Event<Hash>>,
+) -> Boundaries<Hash::State> {
+    let mut boundaries = Vec::new();
+    let mut offset = 0;
+    let mut eof = false;
+    for event in events {
+        assert!(!eof, "Delimited goes on after Eof");
+        match event {
+            Event::Data(_) => offset += 1,
+            // `Delimited` always ends with `Eof`, even after a chunk that
+            // ends with the input, and the other chunkers do not.
+            Event::Boundary(Boundary::Eof(state)) => {
+                eof = true;
+                if boundaries.last().map_or(0, |&(end, _)| end) < offset {
+                    boundaries.push((offset, Mark::Eof(state)));
+                }
+            }
+            Event::Boundary(bd) => boundaries.push((offset, Mark::of(bd))),
+        }
+    }
+    assert!(eof, "Delimited ends without Eof");
+
+    boundaries
+}
+
+/// The boundaries of the chunks of a `Distances`.
+pub fn distances<Hash: Hasher>(
+    extends: impl Iterator<Item = Extend<Hash>>,
+) -> Boundaries<Hash::State> {
+    let mut offset = 0;
+
+    extends
+        .map(|ext| {
+            offset += ext.length.get();
+            (offset, Mark::of(ext.boundary))
+        })
+        .collect()
+}
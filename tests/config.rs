@@ -0,0 +1,221 @@
+//! Checks that configuration identifiers parse into the configurations that
+//! write them, that malformed ones are rejected with the right errors, and
+//! that configurations parsed from them chunk without overflowing.
+
+mod common;
+
+use hashsplit::algorithms::{
+    Any, Bozo32, BupRollsum, Buzhash32, Cp32, Cp64, Gear64, Rabin53, Rrs1, Rrs64,
+};
+use hashsplit::config::{DynConfig, ParseError};
+use hashsplit::Named;
+
+/// The identifier written by the configuration parsed from `identifier`.
+fn reparse(identifier: &str) -> Result<String, ParseError> {
+    identifier
+        .parse::<DynConfig<Any>>()
+        .map(|cfg| cfg.to_string())
+}
+
+#[test]
+fn round_trip_every_algorithm() {
+    let names = [
+        Bozo32::NAME,
+        BupRollsum::NAME,
+        Buzhash32::NAME,
+        Cp32::NAME,
+        Cp64::NAME,
+        Gear64::NAME,
+        Rabin53::NAME,
+        Rrs1::NAME,
+        Rrs64::NAME,
+    ];
+
+    for name in names {
+        let hasher = Any::from_name(name).unwrap();
+        assert_eq!(hasher.name(), name);
+
+        for cfg in [
+            DynConfig::new(hasher, 13, 0x01_00_00, 0x20_00_00),
+            DynConfig::new(hasher, 0, 0, 0),
+            DynConfig::new(hasher, 10, 256, 4096).normalized(1024),
+        ] {
+            let identifier = cfg.to_string();
+            let parsed: DynConfig<Any> = identifier.parse().unwrap();
+
+            assert_eq!(parsed.hasher.name(), name, "{}", identifier);
+            assert_eq!(
+                (
+                    parsed.threshold,
+                    parsed.min_size,
+                    parsed.normal_size,
+                    parsed.max_size
+                ),
+                (cfg.threshold, cfg.min_size, cfg.normal_size, cfg.max_size),
+                "{}",
+                identifier
+            );
+            assert_eq!(parsed.to_string(), identifier);
+        }
+    }
+}
+
+#[test]
+fn sizes() {
+    assert_eq!(
+        reparse("HashSplit_13_cp32_W64_1024_1048576"),
+        Ok("HashSplit_13_cp32_W64_1Ki_1Mi".to_string())
+    );
+    assert_eq!(
+        reparse("HashSplit_13_cp32_W64_3Ki_1Gi"),
+        Ok("HashSplit_13_cp32_W64_3Ki_1Gi".to_string())
+    );
+    assert_eq!(
+        reparse("HashSplit_13_cp32_W64_1000_2047Ki"),
+        Ok("HashSplit_13_cp32_W64_1000_2047Ki".to_string())
+    );
+    assert_eq!(
+        reparse("HashSplit_13_cp32_W64_0_1024Mi"),
+        Ok("HashSplit_13_cp32_W64_0_1Gi".to_string())
+    );
+
+    let parsed: DynConfig<Any> = "HashSplit_13_cp32_W64_2Ki_3Gi".parse().unwrap();
+    assert_eq!((parsed.min_size, parsed.max_size), (2 << 10, 3 << 30));
+}
+
+#[test]
+fn legacy_without_window() {
+    assert_eq!(
+        reparse("HashSplit_13_cp32_64Ki_2Mi"),
+        Ok("HashSplit_13_cp32_W64_64Ki_2Mi".to_string())
+    );
+    assert_eq!(
+        reparse("HashSplit_13_bup_0_8Ki"),
+        Ok("HashSplit_13_bup_W64_0_8Ki".to_string())
+    );
+    assert_eq!(
+        reparse("HashSplit_13_RRS1_64Ki_2Mi"),
+        Err(ParseError::Outdated)
+    );
+}
+
+#[test]
+fn rejects_malformed() {
+    let cases = [
+        ("HashSplit_10_cp32", ParseError::Malformed),
+        ("Hashsplit_13_cp32_W64_64Ki_2Mi", ParseError::Malformed),
+        ("HashSplit_13_cp32_W64_64Ki_2Mi_N1Mi", ParseError::Malformed),
+        (
+            "HashSplit_13_cp32_W64_64Ki_2Mi_1Mi_L2",
+            ParseError::Malformed,
+        ),
+        ("HashSplit_x_cp32_W64_64Ki_2Mi", ParseError::BadThreshold),
+        ("HashSplit__cp32_W64_64Ki_2Mi", ParseError::BadThreshold),
+        (
+            "HashSplit_4294967296_cp32_W64_64Ki_2Mi",
+            ParseError::BadThreshold,
+        ),
+        ("HashSplit_W_cp32_W64_64Ki_2Mi", ParseError::BadThreshold),
+        ("HashSplit_13_cp32_W_64Ki_2Mi", ParseError::BadWindow),
+        ("HashSplit_13_cp32_Wx_64Ki_2Mi", ParseError::BadWindow),
+        ("HashSplit_13_cp32_W-64_64Ki_2Mi", ParseError::BadWindow),
+        (
+            "HashSplit_13_cp32_W48_64Ki_2Mi",
+            ParseError::UnsupportedWindow,
+        ),
+        ("HashSplit_13_cp32_W64_2Mi_64Ki", ParseError::BadSize),
+        ("HashSplit_13_cp32_W64_64Ti_2Mi", ParseError::BadSize),
+        ("HashSplit_13_cp32_W64_Ki_2Mi", ParseError::BadSize),
+        (
+            "HashSplit_10_gear64_W64_256_4Ki_N8Ki_L2",
+            ParseError::BadSize,
+        ),
+        ("HashSplit_10_gear64_W64_256_4Ki_N0_L2", ParseError::BadSize),
+        (
+            "HashSplit_10_gear64_W64_256_4Ki_N1Ki_Lx",
+            ParseError::BadNormalization,
+        ),
+        (
+            "HashSplit_10_gear64_W64_256_4Ki_N1Ki_L3",
+            ParseError::UnsupportedNormalization,
+        ),
+        (
+            "HashSplit_13_RRS2_W64_64Ki_2Mi",
+            ParseError::UnknownAlgorithm,
+        ),
+        (
+            "HashSplit_13_cp32keyed_W64_64Ki_2Mi",
+            ParseError::KeyRequired,
+        ),
+    ];
+
+    for (identifier, error) in cases {
+        assert_eq!(reparse(identifier), Err(error), "{}", identifier);
+    }
+}
+
+#[test]
+fn rejects_plus_signs() {
+    // `FromStr` for integers accepts a leading `+`, which would give one
+    // configuration two identifiers.
+    let cases = [
+        ("HashSplit_+13_cp32_W64_64Ki_2Mi", ParseError::BadThreshold),
+        ("HashSplit_13_cp32_W+64_64Ki_2Mi", ParseError::BadWindow),
+        ("HashSplit_13_cp32_W64_+64Ki_2Mi", ParseError::BadSize),
+        ("HashSplit_13_cp32_W64_64Ki_+2097152", ParseError::BadSize),
+        (
+            "HashSplit_10_gear64_W64_256_4Ki_N+1Ki_L2",
+            ParseError::BadSize,
+        ),
+        (
+            "HashSplit_10_gear64_W64_256_4Ki_N1Ki_L+2",
+            ParseError::BadNormalization,
+        ),
+    ];
+
+    for (identifier, error) in cases {
+        assert_eq!(reparse(identifier), Err(error), "{}", identifier);
+    }
+}
+
+#[test]
+fn rejects_size_overflow() {
+    let cases = [
+        "HashSplit_13_cp32_W64_0_18446744073709551616",
+        "HashSplit_13_cp32_W64_0_18014398509481984Ki",
+        "HashSplit_13_cp32_W64_0_17592186044416Mi",
+        "HashSplit_13_cp32_W64_0_17179869184Gi",
+    ];
+
+    for identifier in cases {
+        assert_eq!(
+            reparse(identifier),
+            Err(ParseError::BadSize),
+            "{}",
+            identifier
+        );
+    }
+}
+
+#[test]
+fn normalized_threshold_saturates() {
+    let data = common::data();
+    let identifier = "HashSplit_4294967295_gear64_W64_256_4Ki_N1Ki_L2";
+    let cfg: DynConfig<Any> = identifier.parse().unwrap();
+    assert_eq!(cfg.threshold, u32::MAX);
+
+    // No checksum reaches the threshold, so every chunk but the last is
+    // capped.
+    let lengths: Vec<_> = cfg
+        .distances(data.iter().copied())
+        .map(|ext| ext.length.get())
+        .collect();
+    assert_eq!(lengths.len(), data.len() / 4096);
+    assert!(lengths.iter().all(|&len| len == 4096));
+
+    let cfg = DynConfig::new(Gear64, u32::MAX, 256, 4096).normalized(1024);
+    assert!(cfg
+        .fast_cdc(data.iter().copied())
+        .map(|ext| ext.length.get())
+        .eq(lengths));
+}
@@ -0,0 +1,234 @@
+//! Checks the chunk boundaries found by each chunker against the test vectors
+//! in `tests/vectors.txt`.
+//!
+//! Each case in the file is a header line giving a configuration identifier,
+//! a seed and a length, followed by a line listing the end offsets of the
+//! chunks of the input generated from that seed and length. An offset is
+//! followed by `:` and the level of the checksum that ended the chunk, or by
+//! `:c` for a chunk that was capped at the maximum size and `:e` for the last
+//! chunk of the input. An empty input, which has no chunks, is listed as `-`.
+//!
+//! Inputs are the little-endian bytes of successive outputs of SplitMix64,
+//! truncated to the length.
+//!
+//! The specification publishes no vectors, and no other implementation has
+//! been run on these inputs, so every case was produced by this crate. The
+//! file only freezes its output: a case that stops matching is a change in
+//! where chunks end, which breaks deduplication against data chunked before
+//! it, and is to be fixed in the code rather than in the file.
+
+#![cfg(feature = "alloc")]
+
+mod common;
+
+use common::Mark;
+use hashsplit::algorithms::buzhash::{Buzhash, Standard};
+use hashsplit::algorithms::Any;
+use hashsplit::config::{DynConfig, Identifier};
+use hashsplit::{Hasher, Leveled, Memoryless, Named, Ring};
+
+use std::fmt::Write as _;
+
+const VECTORS: &str = include_str!("vectors.txt");
+
+type Buzhash48 = Buzhash<Standard, 48>;
+
+/// The cases the file must hold, in order.
+const CASES: &[(&str, u64, usize)] = &[
+    ("HashSplit_6_bup_W64_0_256", 1, 4096),
+    ("HashSplit_10_bup_W64_64_4Ki", 2, 1 << 16),
+    ("HashSplit_13_bup_W64_0_8Ki", 3, 1 << 16),
+    ("HashSplit_6_buzhash32_W64_0_256", 1, 4096),
+    ("HashSplit_10_buzhash32_W64_64_4Ki", 2, 1 << 16),
+    ("HashSplit_13_buzhash32_W64_0_8Ki", 3, 1 << 16),
+    ("HashSplit_6_cp32_W64_0_256", 1, 4096),
+    ("HashSplit_10_cp32_W64_64_4Ki", 2, 1 << 16),
+    ("HashSplit_13_cp32_W64_0_8Ki", 3, 1 << 16),
+    ("HashSplit_6_cp64_W64_0_256", 1, 4096),
+    ("HashSplit_10_cp64_W64_64_4Ki", 2, 1 << 16),
+    ("HashSplit_13_cp64_W64_0_8Ki", 3, 1 << 16),
+    ("HashSplit_6_gear64_W64_0_256", 1, 4096),
+    ("HashSplit_10_gear64_W64_64_4Ki", 2, 1 << 16),
+    ("HashSplit_13_gear64_W64_0_8Ki", 3, 1 << 16),
+    ("HashSplit_6_RRS1_W64_0_256", 1, 4096),
+    ("HashSplit_10_RRS1_W64_64_4Ki", 2, 1 << 16),
+    ("HashSplit_13_RRS1_W64_0_8Ki", 3, 1 << 16),
+    ("HashSplit_6_RRS64_W64_0_256", 1, 4096),
+    ("HashSplit_10_RRS64_W64_64_4Ki", 2, 1 << 16),
+    ("HashSplit_13_RRS64_W64_0_8Ki", 3, 1 << 16),
+    ("HashSplit_6_Bozo32_W64_0_256", 1, 4096),
+    ("HashSplit_10_Bozo32_W64_64_4Ki", 2, 1 << 16),
+    ("HashSplit_13_Bozo32_W64_0_8Ki", 3, 1 << 16),
+    ("HashSplit_10_buzhash32_W48_64_4Ki", 2, 1 << 16),
+    ("HashSplit_6_rabin0x3da3358b4dc173_W64_0_256", 1, 4096),
+    ("HashSplit_10_rabin0x3da3358b4dc173_W64_64_4Ki", 2, 1 << 16),
+    ("HashSplit_13_rabin0x3da3358b4dc173_W64_0_8Ki", 3, 1 << 16),
+    ("HashSplit_8_cp32_W64_0_1Ki", 4, 0),
+    ("HashSplit_8_cp32_W64_0_1Ki", 5, 1),
+    ("HashSplit_8_cp32_W64_0_1Ki", 6, 100),
+    ("HashSplit_8_cp32_W64_16_16", 7, 100),
+];
+
+fn input(seed: u64, len: usize) -> Vec<u8> {
+    let mut state = seed;
+    let mut next = || {
+        state = state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    };
+
+    let mut data = Vec::with_capacity(len + 8);
+    while data.len() < len {
+        data.extend_from_slice(&next().to_le_bytes());
+    }
+    data.truncate(len);
+
+    data
+}
+
+/// Boundaries as the file lists them, without states.
+type Boundaries = common::Boundaries<()>;
+
+fn format(boundaries: &[(usize, Mark<()>)]) -> String {
+    if boundaries.is_empty() {
+        return String::from("-");
+    }
+
+    let mut line = String::new();
+    for (i, (offset, mark)) in boundaries.iter().enumerate() {
+        if i > 0 {
+            line.push(' ');
+        }
+        match mark {
+            Mark::Level(lev, ()) => write!(line, "{}:{}", offset, lev),
+            Mark::Capped(()) => write!(line, "{}:c", offset),
+            Mark::Eof(()) => write!(line, "{}:e", offset),
+        }
+        .unwrap();
+    }
+
+    line
+}
+
+fn parse(line: &str) -> Boundaries {
+    if line == "-" {
+        return Vec::new();
+    }
+
+    line.split_whitespace()
+        .map(|entry| {
+            let (offset, mark) = entry.split_once(':').expect("malformed boundary");
+            let mark = match mark {
+                "c" => Mark::Capped(()),
+                "e" => Mark::Eof(()),
+                lev => Mark::Level(lev.parse().expect("malformed level"), ()),
+            };
+
+            (offset.parse().expect("malformed offset"), mark)
+        })
+        .collect()
+}
+
+/// The boundaries found by each chunker, labeled with its name.
+fn chunk_all<Hash: Memoryless + Copy>(
+    cfg: DynConfig<Hash>,
+    data: &[u8],
+) -> Vec<(&'static str, Boundaries)>
+where
+    Hash::Checksum: Leveled,
+    Hash::State: Clone,
+{
+    let forget = |boundaries: common::Boundaries<Hash::State>| {
+        boundaries
+            .into_iter()
+            .map(|(offset, mark)| (offset, mark.forget()))
+            .collect()
+    };
+    let delimited = forget(common::delimited(cfg.delimited(data.iter().copied())));
+    let distances = forget(common::distances(cfg.distances(data.iter().copied())));
+
+    let spans = offsets(cfg.spans(data));
+    let indexed_spans = offsets(cfg.indexed_spans(data));
+
+    vec![
+        ("Delimited", delimited),
+        ("Distances", distances),
+        ("Spans", spans),
+        ("IndexedSpans", indexed_spans),
+    ]
+}
+
+fn offsets<'a, Hash: Hasher + 'a>(
+    chunks: impl Iterator<Item = hashsplit::chunk::ResumableChunk<'a, Hash>>,
+) -> Boundaries {
+    let mut offset = 0;
+    let mut chunks = chunks.peekable();
+    let mut boundaries = Vec::new();
+    while let Some(chunk) = chunks.next() {
+        offset += chunk.len();
+        let mark = match chunk.level {
+            Some(lev) => Mark::Level(lev, ()),
+            None if chunks.peek().is_none() => Mark::Eof(()),
+            None => Mark::Capped(()),
+        };
+        boundaries.push((offset, mark));
+    }
+
+    boundaries
+}
+
+fn run(identifier: &str, data: &[u8]) -> Vec<(&'static str, Boundaries)> {
+    let id = Identifier::parse(identifier).unwrap();
+    // `Any` only holds hashers with the default window.
+    if (id.name, id.window) == (Buzhash48::NAME, <<Buzhash48 as Hasher>::Ring as Ring>::LEN) {
+        let cfg = DynConfig::new(Buzhash48::default(), id.threshold, id.min_size, id.max_size);
+        return chunk_all(cfg, data);
+    }
+
+    chunk_all(identifier.parse::<DynConfig<Any>>().unwrap(), data)
+}
+
+fn cases() -> impl Iterator<Item = (&'static str, u64, usize, Boundaries)> {
+    let mut lines = VECTORS
+        .lines()
+        .filter(|line| !line.is_empty() && !line.starts_with('#'));
+
+    std::iter::from_fn(move || {
+        let header = lines.next()?;
+        let boundaries = lines.next().expect("missing boundaries");
+        let mut fields = header.split_whitespace();
+        let identifier = fields.next().unwrap();
+        let seed = fields.next().unwrap().parse().unwrap();
+        let len = fields.next().unwrap().parse().unwrap();
+
+        Some((identifier, seed, len, parse(boundaries)))
+    })
+}
+
+#[test]
+fn vectors() {
+    let mut headers = Vec::new();
+    for (identifier, seed, len, expected) in cases() {
+        let data = input(seed, len);
+        if let Some(&(offset, _)) = expected.last() {
+            assert_eq!(offset, len, "{} {} {}", identifier, seed, len);
+        }
+
+        for (chunker, found) in run(identifier, &data) {
+            assert!(
+                found == expected,
+                "{} disagrees on {} {} {}:\n{}",
+                chunker,
+                identifier,
+                seed,
+                len,
+                format(&found)
+            );
+        }
+        headers.push((identifier, seed, len));
+    }
+
+    assert_eq!(headers, CASES);
+}
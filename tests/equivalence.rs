@@ -0,0 +1,257 @@
+//! Checks that every chunker finds the same boundaries, with the same states,
+//! on arbitrary inputs and limits.
+
+#![cfg(feature = "alloc")]
+
+mod common;
+
+use common::{delimited, distances, inputs, limits, Boundaries, Mark};
+use hashsplit::algorithms::{
+    Bozo32, BupRollsum, Buzhash32, Cp32, Cp64, Gear64, Rabin, Rrs1, Rrs64,
+};
+use hashsplit::config::{DynConfig, DynLimits};
+use hashsplit::iter::{FastCdc, IndexedSpans, Scanner};
+use hashsplit::{Config, Hasher, Leveled, Memoryless, WINDOW_SIZE};
+
+use proptest::prelude::*;
+use std::cell::Cell;
+use std::fmt::Debug;
+
+fn splits<Hash: Hasher + Copy>(cfg: DynConfig<Hash>, data: &[u8]) -> Vec<(usize, Option<u32>)>
+where
+    Hash::Checksum: Leveled,
+    Hash::State: Clone,
+{
+    let mut offset = 0;
+
+    cfg.delimited(data.iter().copied())
+        .splits()
+        .map(|chunk| {
+            offset += chunk.len();
+            (offset, chunk.level)
+        })
+        .collect()
+}
+
+fn spans<'a, Hash: Hasher + 'a>(
+    chunks: impl Iterator<Item = hashsplit::chunk::ResumableChunk<'a, Hash>>,
+) -> Vec<(usize, Option<u32>, Hash::State)> {
+    let mut offset = 0;
+
+    chunks
+        .map(|chunk| {
+            offset += chunk.len();
+            (offset, chunk.level, chunk.state)
+        })
+        .collect()
+}
+
+fn scanner<Hash: Hasher + Copy>(
+    cfg: DynConfig<Hash>,
+    data: &[u8],
+    piece: usize,
+) -> Boundaries<Hash::State>
+where
+    Hash::Checksum: Leveled,
+    Hash::State: Clone,
+{
+    let mut scanner = Scanner::start(cfg.hasher, cfg.limits());
+    let mut boundaries = Vec::new();
+    let mut offset = 0;
+    for mut rest in data.chunks(piece) {
+        while !rest.is_empty() {
+            let (consumed, bd) = scanner.scan(rest);
+            offset += consumed;
+            rest = &rest[consumed..];
+            boundaries.extend(bd.map(|bd| (offset, Mark::of(bd))));
+        }
+    }
+    boundaries.extend(scanner.finish().map(|bd| (offset, Mark::of(bd))));
+
+    boundaries
+}
+
+fn check<Hash: Memoryless + Copy>(cfg: DynConfig<Hash>, data: &[u8], piece: usize)
+where
+    Hash::Checksum: Leveled,
+    Hash::State: Clone + PartialEq + Debug,
+{
+    let expected = delimited(cfg.delimited(data.iter().copied()));
+
+    assert_eq!(distances(cfg.distances(data.iter().copied())), expected);
+    assert_eq!(scanner(cfg, data, piece), expected);
+
+    let chunks: Vec<_> = expected
+        .iter()
+        .map(|(offset, mark)| match mark {
+            Mark::Level(lev, state) => (*offset, Some(*lev), state.clone()),
+            Mark::Capped(state) | Mark::Eof(state) => (*offset, None, state.clone()),
+        })
+        .collect();
+    let levels: Vec<_> = chunks
+        .iter()
+        .map(|&(offset, lev, _)| (offset, lev))
+        .collect();
+
+    assert_eq!(splits(cfg, data), levels);
+    assert_eq!(spans(cfg.spans(data)), chunks);
+    assert_eq!(spans(cfg.indexed_spans(data)), chunks);
+}
+
+/// [`Cp32`], counting the bytes it hashes.
+#[derive(Clone, Copy)]
+struct Counting<'a>(&'a Cell<usize>);
+
+impl Hasher for Counting<'_> {
+    type Checksum = u32;
+
+    type State = u32;
+
+    type Ring = [u8; WINDOW_SIZE];
+
+    const INITIAL_STATE: u32 = Cp32::INITIAL_STATE;
+
+    fn process_byte(&self, state: u32, old_byte: u8, new_byte: u8) -> (u32, u32) {
+        self.0.set(self.0.get() + 1);
+        Cp32.process_byte(state, old_byte, new_byte)
+    }
+}
+
+impl Memoryless for Counting<'_> {}
+
+#[test]
+fn indexed_spans_skips_without_max_size() {
+    let data: Vec<u8> = (0..8192u32).map(|i| (i * 7 + i / 13) as u8).collect();
+    let hashed = Cell::new(0);
+    let limits = DynLimits {
+        threshold: 32,
+        min_size: 8000,
+        normal_size: 0,
+        max_size: 0,
+    };
+
+    let lengths: Vec<_> = IndexedSpans::start(Counting(&hashed), &data, limits)
+        .map(|chunk| chunk.len())
+        .collect();
+    assert_eq!(lengths, [data.len()]);
+    // Only the window before the first byte that can end the chunk, and the
+    // bytes from there on.
+    assert_eq!(hashed.get(), WINDOW_SIZE + data.len() - 7999);
+}
+
+#[test]
+fn same_as_config() {
+    let data = common::data();
+    let cfg: Config<Cp32, 8, 64, 1024> = Default::default();
+    let dyn_cfg = DynConfig::new(Cp32, 8, 64, 1024);
+
+    assert_eq!(spans(dyn_cfg.spans(&data)), spans(cfg.spans(&data)));
+    assert_eq!(dyn_cfg.to_string(), cfg.to_string());
+}
+
+proptest! {
+    #[test]
+    fn bozo32(
+        (threshold, min, max) in limits(10, 300, 1000),
+        data in inputs(4096),
+        piece in 1usize..500,
+    ) {
+        check(DynConfig::new(Bozo32, threshold, min, max), &data, piece);
+    }
+
+    #[test]
+    fn bup(
+        (threshold, min, max) in limits(10, 300, 1000),
+        data in inputs(4096),
+        piece in 1usize..500,
+    ) {
+        check(DynConfig::new(BupRollsum, threshold, min, max), &data, piece);
+    }
+
+    #[test]
+    fn buzhash32(
+        (threshold, min, max) in limits(10, 300, 1000),
+        data in inputs(4096),
+        piece in 1usize..500,
+    ) {
+        check(DynConfig::new(Buzhash32::default(), threshold, min, max), &data, piece);
+    }
+
+    #[test]
+    fn cp32(
+        (threshold, min, max) in limits(10, 300, 1000),
+        data in inputs(4096),
+        piece in 1usize..500,
+    ) {
+        check(DynConfig::new(Cp32, threshold, min, max), &data, piece);
+    }
+
+    #[test]
+    fn cp64(
+        (threshold, min, max) in limits(10, 300, 1000),
+        data in inputs(4096),
+        piece in 1usize..500,
+    ) {
+        check(DynConfig::new(Cp64, threshold, min, max), &data, piece);
+    }
+
+    #[test]
+    fn gear64(
+        (threshold, min, max) in limits(10, 300, 1000),
+        data in inputs(4096),
+        piece in 1usize..500,
+    ) {
+        let cfg = DynConfig::new(Gear64, threshold, min, max);
+        check(cfg, &data, piece);
+        prop_assert_eq!(
+            distances(cfg.fast_cdc(data.iter().copied())),
+            delimited(cfg.delimited(data.iter().copied()))
+        );
+    }
+
+    #[test]
+    fn gear64_normalized(
+        (threshold, min, max) in limits(10, 300, 1000),
+        normal in 0usize..1300,
+        data in inputs(4096),
+        piece in 1usize..500,
+    ) {
+        let normal = if max == 0 { normal.max(min) } else { normal.clamp(min, max) };
+        let cfg = DynConfig::new(Gear64, threshold, min, max).normalized(normal);
+        check(cfg, &data, piece);
+
+        let limits = DynLimits { threshold, min_size: min, normal_size: normal, max_size: max };
+        prop_assert_eq!(
+            distances(FastCdc::start(Gear64, data.iter().copied(), limits)),
+            delimited(cfg.delimited(data.iter().copied()))
+        );
+    }
+
+    #[test]
+    fn rrs1(
+        (threshold, min, max) in limits(10, 300, 1000),
+        data in inputs(4096),
+        piece in 1usize..500,
+    ) {
+        check(DynConfig::new(Rrs1::default(), threshold, min, max), &data, piece);
+    }
+
+    #[test]
+    fn rrs64(
+        (threshold, min, max) in limits(10, 300, 1000),
+        data in inputs(4096),
+        piece in 1usize..500,
+    ) {
+        check(DynConfig::new(Rrs64::default(), threshold, min, max), &data, piece);
+    }
+
+    #[test]
+    fn rabin(
+        (threshold, min, max) in limits(10, 300, 1000),
+        data in inputs(4096),
+        piece in 1usize..500,
+    ) {
+        let hasher = Rabin::<0x3d_a3_35_8b_4d_c1_73>;
+        check(DynConfig::new(hasher, threshold, min, max), &data, piece);
+    }
+}
@@ -0,0 +1,55 @@
+//! Checks that chunking a reader gives the same chunks as chunking the slice,
+//! whatever the size of the buffer and of each read.
+
+#![cfg(feature = "std")]
+
+mod common;
+
+use hashsplit::algorithms::Cp32;
+use hashsplit::config::DynConfig;
+use hashsplit::io::ReadChunks;
+
+use std::io::{self, Read};
+
+/// A reader that gives out at most `step` bytes at a time.
+struct Trickle<'a> {
+    data: &'a [u8],
+    step: usize,
+}
+
+impl Read for Trickle<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let n = buf.len().min(self.step).min(self.data.len());
+        buf[..n].copy_from_slice(&self.data[..n]);
+        self.data = &self.data[n..];
+
+        Ok(n)
+    }
+}
+
+#[test]
+fn same_as_spans() {
+    let data = common::data();
+    let cfg = DynConfig::new(Cp32, 8, 64, 1024);
+    let expected: Vec<_> = cfg
+        .spans(&data)
+        .map(|chunk| (chunk.to_vec(), chunk.level, chunk.state))
+        .collect();
+
+    for buffer_size in [1, 100, 1024, 5000, 1 << 16] {
+        for step in [1, 333, usize::MAX] {
+            let reader = Trickle { data: &data, step };
+            let chunks: Vec<_> =
+                ReadChunks::with_buffer_size(cfg.hasher, reader, cfg.limits(), buffer_size)
+                    .map(|chunk| chunk.map(|chunk| (chunk.to_vec(), chunk.level, chunk.state)))
+                    .collect::<io::Result<_>>()
+                    .unwrap();
+
+            assert_eq!(
+                chunks, expected,
+                "buffer of {}, reads of {}",
+                buffer_size, step
+            );
+        }
+    }
+}
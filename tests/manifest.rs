@@ -0,0 +1,200 @@
+//! Checks that manifests survive encoding and that malformed ones are
+//! rejected with the right errors.
+
+#![cfg(feature = "alloc")]
+
+mod common;
+
+use common::{data, Fnv};
+use hashsplit::algorithms::Cp32;
+use hashsplit::chunk::{ChunkRef, Outline, OutlineNode, TreeBuilder};
+use hashsplit::config::ParseError;
+use hashsplit::digest::Digest;
+use hashsplit::manifest::{DecodeError, Manifest, MAGIC, MAX_DEPTH, VERSION};
+use hashsplit::Config;
+
+use proptest::prelude::*;
+
+const CFG: Config<Cp32, 6, 64, 1024> = Config { hasher: Cp32 };
+
+const ID: &[u8] = b"HashSplit_6_cp32_W64_64_1Ki";
+
+fn manifest() -> Manifest<Fnv> {
+    let data = data();
+    let mut builder = TreeBuilder::<_, Fnv>::new(6);
+    builder.extend(CFG.spans(&data));
+
+    Manifest {
+        identifier: CFG.to_string(),
+        outline: builder.finish().unwrap().outline(),
+    }
+}
+
+fn chunks(node: &OutlineNode<Fnv>) -> Vec<([u8; 8], u64, Option<u32>)> {
+    match node {
+        OutlineNode::Internal { children, .. } => children.iter().flat_map(chunks).collect(),
+        OutlineNode::Leaf { chunks, .. } => chunks
+            .iter()
+            .map(|chunk| (chunk.digest, chunk.len, chunk.level))
+            .collect(),
+    }
+}
+
+/// The start of a manifest with the given identifier and root digest.
+fn header(identifier: &[u8], root: [u8; 8]) -> Vec<u8> {
+    let mut out = MAGIC.to_vec();
+    out.extend([VERSION, 8, Fnv::NAME.len() as u8]);
+    out.extend(Fnv::NAME.as_bytes());
+    out.push(identifier.len() as u8);
+    out.extend(identifier);
+    out.extend(root);
+
+    out
+}
+
+#[test]
+fn round_trip() {
+    let manifest = manifest();
+    let bytes = manifest.encode();
+    let decoded = Manifest::<Fnv>::decode(&bytes).unwrap();
+
+    assert_eq!(decoded.identifier, "HashSplit_6_cp32_W64_64_1Ki");
+    assert_eq!(decoded.outline.digest(), manifest.outline.digest());
+    assert_eq!(decoded.outline.len(), data().len() as u64);
+    assert!(matches!(decoded.outline.root, OutlineNode::Internal { .. }));
+    assert!(decoded.outline.root.verify());
+    assert_eq!(
+        chunks(&decoded.outline.root),
+        chunks(&manifest.outline.root)
+    );
+    assert!(chunks(&decoded.outline.root)
+        .iter()
+        .any(|chunk| chunk.2.is_some()));
+    assert_eq!(decoded.encode(), bytes);
+}
+
+#[test]
+fn round_trip_empty() {
+    let manifest = Manifest::<Fnv> {
+        identifier: CFG.to_string(),
+        outline: Outline::from_chunks(Vec::new()),
+    };
+    let decoded = Manifest::<Fnv>::decode(&manifest.encode()).unwrap();
+
+    assert!(decoded.outline.is_empty());
+    assert_eq!(decoded.outline.digest(), manifest.outline.digest());
+}
+
+#[test]
+fn rejects_malformed() {
+    let bytes = manifest().encode();
+    let decode = |bytes: &[u8]| Manifest::<Fnv>::decode(bytes).err();
+
+    let mut bad = bytes.clone();
+    bad[0] = b'X';
+    assert_eq!(decode(&bad), Some(DecodeError::BadMagic));
+
+    let mut bad = bytes.clone();
+    bad[4] = VERSION + 1;
+    assert_eq!(
+        decode(&bad),
+        Some(DecodeError::UnsupportedVersion(VERSION + 1))
+    );
+
+    let mut bad = bytes.clone();
+    bad[5] = 4;
+    assert_eq!(decode(&bad), Some(DecodeError::DigestLength(4)));
+
+    // Another digest whose name has the same length.
+    let mut bad = bytes.clone();
+    bad[7] = b'F';
+    assert_eq!(decode(&bad), Some(DecodeError::WrongDigest));
+
+    for len in 0..bytes.len() {
+        assert_eq!(decode(&bytes[..len]), Some(DecodeError::Truncated));
+    }
+
+    let mut bad = bytes.clone();
+    bad.push(0);
+    assert_eq!(decode(&bad), Some(DecodeError::TrailingBytes));
+
+    // Flip a bit of the first chunk digest.
+    let mut bad = bytes.clone();
+    let mut first_digest = header(CFG.to_string().as_bytes(), [0; 8]).len();
+    while bad[first_digest] == 1 {
+        // Skip the kind byte and child count of internal nodes.
+        first_digest += 2;
+    }
+    bad[first_digest + 2] ^= 1;
+    assert_eq!(decode(&bad), Some(DecodeError::DigestMismatch));
+
+    let mut bad = header(b"\xff", [0; 8]);
+    bad.extend([0, 0]);
+    assert_eq!(
+        decode(&bad),
+        Some(DecodeError::BadIdentifier(ParseError::Malformed))
+    );
+
+    // Identifiers are parsed whatever their algorithm, but must be well-formed.
+    let mut bad = header(b"HashSplit_6_cp32_W64_1Ki_64", [0; 8]);
+    bad.extend([0, 0]);
+    assert_eq!(
+        decode(&bad),
+        Some(DecodeError::BadIdentifier(ParseError::BadSize))
+    );
+    let empty = Outline::<Fnv>::from_chunks(Vec::new());
+    let mut good = header(b"HashSplit_6_someday_W48_64_1Ki", *empty.digest());
+    good.extend([0, 0]);
+    assert!(Manifest::<Fnv>::decode(&good).is_ok());
+
+    let mut bad = header(ID, [0; 8]);
+    bad.extend([2, 0]);
+    assert_eq!(decode(&bad), Some(DecodeError::BadNodeKind(2)));
+
+    let mut bad = header(ID, [0; 8]);
+    bad.extend([1, 0]);
+    assert_eq!(decode(&bad), Some(DecodeError::EmptyNode));
+
+    // An empty leaf below the root.
+    let mut bad = header(ID, [0; 8]);
+    bad.extend([1, 1, 0, 0]);
+    assert_eq!(decode(&bad), Some(DecodeError::EmptyNode));
+
+    let mut bad = header(ID, [0; 8]);
+    for _ in 0..MAX_DEPTH {
+        bad.extend([1, 1]);
+    }
+    bad.extend([0, 0]);
+    assert_eq!(decode(&bad), Some(DecodeError::TooDeep));
+
+    let mut bad = MAGIC.to_vec();
+    bad.extend([VERSION, 8]);
+    bad.extend([0x80; 10]);
+    bad.push(0);
+    assert_eq!(decode(&bad), Some(DecodeError::BadInteger));
+
+    let chunk = Fnv::digest(b"chunk");
+    let leaf = Outline::<Fnv>::from_chunks(vec![ChunkRef {
+        digest: chunk,
+        len: 5,
+        level: None,
+    }]);
+    let mut bad = header(ID, *leaf.digest());
+    bad.extend([0, 1]);
+    bad.extend(chunk);
+    bad.extend([5, 0x80, 0x80, 0x80, 0x80, 0x20]);
+    assert_eq!(decode(&bad), Some(DecodeError::BadInteger));
+}
+
+proptest! {
+    #[test]
+    fn never_panics(edits in proptest::collection::vec((any::<prop::sample::Index>(), any::<u8>()), 1..8)) {
+        let mut bytes = manifest().encode();
+        for (index, byte) in edits {
+            let i = index.index(bytes.len());
+            bytes[i] = byte;
+        }
+
+        let _ = Manifest::<Fnv>::decode(&bytes);
+    }
+}
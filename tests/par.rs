@@ -0,0 +1,66 @@
+//! Checks that chunking segments in parallel gives the same chunks as
+//! chunking the whole input in order.
+
+#![cfg(feature = "rayon")]
+
+mod common;
+
+use common::{inputs, limits};
+use hashsplit::algorithms::{Bozo32, BupRollsum, Buzhash32, Cp32, Gear64, Rrs1, Rrs64};
+use hashsplit::config::DynConfig;
+use hashsplit::par::par_spans;
+use hashsplit::{Leveled, Memoryless};
+
+use proptest::prelude::*;
+use std::fmt::Debug;
+
+fn same_as_spans<Hash>(cfg: DynConfig<Hash>, data: &[u8], segment_size: usize)
+where
+    Hash: Memoryless + Copy + Send + Sync,
+    Hash::Checksum: Leveled,
+    Hash::State: Clone + PartialEq + Debug + Send,
+{
+    let parallel: Vec<_> = par_spans(cfg.hasher, data, cfg.limits(), segment_size)
+        .into_iter()
+        .map(|chunk| (chunk.to_vec(), chunk.level, chunk.state))
+        .collect();
+    let sequential: Vec<_> = cfg
+        .spans(data)
+        .map(|chunk| (chunk.to_vec(), chunk.level, chunk.state))
+        .collect();
+
+    assert_eq!(parallel, sequential);
+}
+
+proptest! {
+    #[test]
+    fn cp32(
+        (threshold, min, max) in limits(8, 300, 1000),
+        data in inputs(4096),
+        segment_size in 1usize..1500,
+    ) {
+        same_as_spans(DynConfig::new(Cp32, threshold, min, max), &data, segment_size);
+    }
+
+    #[test]
+    fn bup(
+        (threshold, min, max) in limits(8, 300, 1000),
+        data in inputs(4096),
+        segment_size in 1usize..1500,
+    ) {
+        same_as_spans(DynConfig::new(BupRollsum, threshold, min, max), &data, segment_size);
+    }
+
+    #[test]
+    fn others(
+        (threshold, min, max) in limits(8, 300, 1000),
+        data in inputs(4096),
+        segment_size in 1usize..1500,
+    ) {
+        same_as_spans(DynConfig::new(Bozo32, threshold, min, max), &data, segment_size);
+        same_as_spans(DynConfig::new(Buzhash32::default(), threshold, min, max), &data, segment_size);
+        same_as_spans(DynConfig::new(Gear64, threshold, min, max), &data, segment_size);
+        same_as_spans(DynConfig::new(Rrs1::default(), threshold, min, max), &data, segment_size);
+        same_as_spans(DynConfig::new(Rrs64::default(), threshold, min, max), &data, segment_size);
+    }
+}
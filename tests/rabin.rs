@@ -0,0 +1,40 @@
+//! Checks Rabin fingerprints against those of LBFS.
+//!
+//! The expected fingerprints were computed with a transcription into C of
+//! `rabinpoly.C` and its `window` class from LBFS, which reduces by long
+//! multiplication of polynomials rather than by the tables of this crate,
+//! once with the polynomial and window of LBFS and once with those of
+//! [`Rabin53`]. They agree with the window read as a polynomial and reduced
+//! directly.
+
+use hashsplit::algorithms::{Rabin, Rabin53};
+use hashsplit::iter::Rolling;
+
+/// The polynomial and window size of LBFS.
+type Lbfs = Rabin<0xbfe6_b8a5_bf37_8d83, 48>;
+
+fn input(len: usize) -> impl Iterator<Item = u8> {
+    b"The quick brown fox jumps over the lazy dog. "
+        .iter()
+        .copied()
+        .cycle()
+        .take(len)
+}
+
+#[test]
+fn lbfs() {
+    let last = |len| Rolling::start(Lbfs::default(), input(len)).last();
+
+    assert_eq!(last(48), Some(0x6ff1_1de0_3a93_d505));
+    assert_eq!(last(64), Some(0x25ab_c0e2_9cc3_2910));
+    assert_eq!(last(1000), Some(0x0c4c_340b_0b3b_ef5b));
+}
+
+#[test]
+fn rabin53() {
+    let last = |len| Rolling::start(Rabin53::default(), input(len)).last();
+
+    assert_eq!(last(48), Some(0x000a_a4ea_1a5f_b131));
+    assert_eq!(last(64), Some(0x001a_0e6d_09c6_7d9d));
+    assert_eq!(last(1000), Some(0x0010_3c59_cec5_bf56));
+}
@@ -0,0 +1,97 @@
+//! Checks that chunking an edited input again from near the edit gives the
+//! same chunks as chunking all of it.
+
+#![cfg(feature = "alloc")]
+
+mod common;
+
+use common::{inputs, limits};
+use hashsplit::algorithms::{BupRollsum, Cp32, Rrs1};
+use hashsplit::config::DynConfig;
+use hashsplit::{Config, Leveled, Memoryless};
+
+use proptest::prelude::*;
+use std::fmt::Debug;
+use std::ops::Range;
+
+fn same_as_distances<Hash>(
+    cfg: DynConfig<Hash>,
+    old_data: &[u8],
+    edited: Range<usize>,
+    inserted: &[u8],
+) where
+    Hash: Memoryless + Copy,
+    Hash::Checksum: Leveled,
+    Hash::State: Clone + PartialEq + Debug,
+{
+    let mut data = old_data.to_vec();
+    data.splice(edited.clone(), inserted.iter().copied());
+
+    let old: Vec<_> = cfg.distances(old_data.iter().copied()).collect();
+    let rechunk = cfg.rechunk(&old, edited, &data);
+    let offset: usize = old[..rechunk.replaced.start]
+        .iter()
+        .map(|ext| ext.length.get())
+        .sum();
+    assert_eq!(rechunk.offset, offset);
+
+    let summary = |ext: &hashsplit::iter::Extend<Hash>| {
+        (
+            ext.length,
+            ext.boundary.level(),
+            ext.boundary.state().clone(),
+        )
+    };
+    let merged: Vec<_> = old[..rechunk.replaced.start]
+        .iter()
+        .chain(&rechunk.chunks)
+        .chain(&old[rechunk.replaced.end..])
+        .map(summary)
+        .collect();
+    let new: Vec<_> = cfg
+        .distances(data.iter().copied())
+        .map(|ext| summary(&ext))
+        .collect();
+
+    assert_eq!(merged, new);
+}
+
+#[test]
+fn replaces_few_chunks() {
+    let mut data = common::data();
+    let cfg: Config<Cp32, 8, 64, 1024> = Default::default();
+    let old: Vec<_> = cfg.distances(data.iter().copied()).collect();
+
+    data.splice(30_000..30_010, b"hello, world!".iter().copied());
+    let rechunk = cfg.rechunk(&old, 30_000..30_010, &data);
+
+    assert!(rechunk.replaced.len() < old.len() / 10);
+}
+
+fn edit() -> impl Strategy<Value = (Vec<u8>, Range<usize>, Vec<u8>)> {
+    inputs(2000).prop_flat_map(|data| {
+        let len = data.len();
+        (
+            Just(data),
+            (0..=len, 0..=len).prop_map(|(a, b)| a.min(b)..a.max(b)),
+            proptest::collection::vec(any::<u8>(), 0..200),
+        )
+    })
+}
+
+proptest! {
+    #[test]
+    fn cp32((threshold, min, max) in limits(6, 100, 300), (data, edited, inserted) in edit()) {
+        same_as_distances(DynConfig::new(Cp32, threshold, min, max), &data, edited, &inserted);
+    }
+
+    #[test]
+    fn bup((threshold, min, max) in limits(6, 100, 300), (data, edited, inserted) in edit()) {
+        same_as_distances(DynConfig::new(BupRollsum, threshold, min, max), &data, edited, &inserted);
+    }
+
+    #[test]
+    fn rrs1((threshold, min, max) in limits(6, 100, 300), (data, edited, inserted) in edit()) {
+        same_as_distances(DynConfig::new(Rrs1::default(), threshold, min, max), &data, edited, &inserted);
+    }
+}
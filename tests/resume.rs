@@ -0,0 +1,65 @@
+//! Checks that chunking resumed after a boundary finds the same boundaries as
+//! chunking the whole input, with both `Distances` and `Delimited`.
+
+mod common;
+
+use common::{delimited, distances, inputs, limits, Boundaries};
+use hashsplit::algorithms::{BupRollsum, Cp32, Rrs1};
+use hashsplit::config::DynConfig;
+use hashsplit::iter::Rolling;
+use hashsplit::{Hasher, Leveled};
+
+use proptest::prelude::*;
+use std::fmt::Debug;
+
+fn same_after_every_boundary<Hash>(cfg: DynConfig<Hash>, data: &[u8])
+where
+    Hash: Hasher + Copy,
+    Hash::Checksum: Leveled,
+    Hash::State: Clone + PartialEq + Debug,
+{
+    let whole = distances(cfg.distances(data.iter().copied()));
+    assert_eq!(delimited(cfg.delimited(data.iter().copied())), whole);
+
+    for (i, (offset, mark)) in whole.iter().enumerate() {
+        let (tail, rest) = data.split_at(*offset);
+        let state = mark.state();
+        let expected: Boundaries<_> = whole[i + 1..]
+            .iter()
+            .map(|(end, mark)| (end - offset, mark.clone()))
+            .collect();
+
+        let resumed = cfg.resume_distances(rest.iter().copied(), state.clone(), tail);
+        assert_eq!(distances(resumed), expected);
+        let resumed = cfg.resume_delimited(rest.iter().copied(), state.clone(), tail);
+        assert_eq!(delimited(resumed), expected);
+    }
+}
+
+proptest! {
+    #[test]
+    fn cp32((threshold, min, max) in limits(6, 100, 300), data in inputs(1000)) {
+        same_after_every_boundary(DynConfig::new(Cp32, threshold, min, max), &data);
+    }
+
+    #[test]
+    fn bup((threshold, min, max) in limits(6, 100, 300), data in inputs(1000)) {
+        same_after_every_boundary(DynConfig::new(BupRollsum, threshold, min, max), &data);
+    }
+
+    #[test]
+    fn rrs1((threshold, min, max) in limits(6, 100, 300), data in inputs(1000)) {
+        same_after_every_boundary(DynConfig::new(Rrs1::default(), threshold, min, max), &data);
+    }
+
+    #[test]
+    fn rolling(data in proptest::collection::vec(any::<u8>(), 0..300), split in 0usize..300) {
+        let split = split.min(data.len());
+        let mut first = Rolling::start(Rrs1::default(), data[..split].iter().copied());
+        let mut sums: Vec<_> = first.by_ref().collect();
+        let state = *first.state();
+        sums.extend(Rolling::resume(Rrs1::default(), data[split..].iter().copied(), state, &data[..split]));
+
+        assert_eq!(sums, Rolling::start(Rrs1::default(), data.iter().copied()).collect::<Vec<_>>());
+    }
+}
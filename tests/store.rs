@@ -0,0 +1,234 @@
+//! Checks that the chunk stores keep chunks once and report duplicates.
+
+#![cfg(feature = "store")]
+
+mod common;
+
+use common::{data, Fnv};
+use hashsplit::algorithms::Cp32;
+use hashsplit::digest::Digest;
+use hashsplit::store::{ChunkStore, MemoryStore};
+use hashsplit::Config;
+
+fn keeps_chunks_once<S: ChunkStore<Fnv>>(store: &mut S)
+where
+    S::Error: std::fmt::Debug,
+{
+    let data = data();
+    let cfg: Config<Cp32, 8, 64, 1024> = Default::default();
+    let chunks: Vec<_> = cfg.spans(&data).collect();
+
+    let first = store.put_chunks(cfg.spans(&data)).unwrap();
+    assert_eq!(first.digests.len(), chunks.len());
+    assert_eq!(first.duplicates, 0);
+    assert_eq!(first.duplicate_bytes, 0);
+
+    for (digest, chunk) in first.digests.iter().zip(&chunks) {
+        assert!(store.has(digest).unwrap());
+        assert_eq!(&*store.get(digest).unwrap().unwrap(), &**chunk);
+    }
+
+    let second = store
+        .put_chunks(cfg.delimited(data.iter().copied()).splits())
+        .unwrap();
+    assert_eq!(second.digests, first.digests);
+    assert_eq!(second.duplicates, chunks.len());
+    assert_eq!(second.duplicate_bytes, data.len() as u64);
+
+    let put = store.put(b"not a chunk of the data").unwrap();
+    assert!(!put.duplicate);
+    assert!(store.put(b"not a chunk of the data").unwrap().duplicate);
+
+    let missing = Fnv::digest(b"never stored");
+    assert!(!store.has(&missing).unwrap());
+    assert!(store.get(&missing).unwrap().is_none());
+}
+
+#[test]
+fn memory() {
+    let mut store = MemoryStore::new();
+    keeps_chunks_once(&mut store);
+    assert!(!store.is_empty());
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn dir() {
+    use hashsplit::digest::chunk_digest;
+    use hashsplit::store::DirStore;
+
+    let root = std::env::temp_dir().join(format!("hashsplit-store-{}", std::process::id()));
+    let mut store = DirStore::open(&root).unwrap();
+    keeps_chunks_once(&mut store);
+
+    let digest = chunk_digest::<Fnv>(b"not a chunk of the data");
+    assert!(store.path(&digest).is_file());
+
+    // Two stores on the same directory, as in two processes, keep a chunk
+    // once and leave no temporary files behind.
+    let mut other = DirStore::<Fnv>::open(&root).unwrap();
+    let put = other.put(b"another chunk").unwrap();
+    assert!(!put.duplicate);
+    assert!(store.put(b"another chunk").unwrap().duplicate);
+    for entry in std::fs::read_dir(&root).unwrap() {
+        let path = entry.unwrap().path();
+        assert_ne!(
+            path.extension().unwrap_or_default(),
+            "partial",
+            "{}",
+            path.display()
+        );
+    }
+
+    std::fs::remove_dir_all(&root).unwrap();
+}
+
+#[cfg(feature = "std")]
+mod reader {
+    use crate::common::{data, Fnv};
+    use hashsplit::algorithms::Cp32;
+    use hashsplit::chunk::{ChunkRef, Outline, OutlineNode, TreeBuilder};
+    use hashsplit::digest::{chunk_digest, leaf_digest, Digest};
+    use hashsplit::store::{ChunkStore, MemoryStore, TreeReader};
+    use hashsplit::Config;
+
+    use std::io::{ErrorKind, Read, Seek, SeekFrom};
+
+    const CFG: Config<Cp32, 6, 64, 1024> = Config { hasher: Cp32 };
+
+    fn outline(data: &[u8]) -> Outline<Fnv> {
+        let mut builder = TreeBuilder::<_, Fnv>::new(6);
+        builder.extend(CFG.spans(data));
+
+        builder.finish().unwrap().outline()
+    }
+
+    #[test]
+    fn reads_and_seeks() {
+        let data = data();
+        let mut store = MemoryStore::<Fnv>::new();
+        store.put_chunks(CFG.spans(&data)).unwrap();
+        let outline = outline(&data);
+        assert_eq!(outline.len(), data.len() as u64);
+
+        let mut reader = TreeReader::new(outline, &store).unwrap();
+        let mut restored = Vec::new();
+        reader.read_to_end(&mut restored).unwrap();
+        assert_eq!(restored, data);
+
+        for &(pos, len) in &[(0, 10), (5000, 3000), (65_000, 536), (12_345, 1)] {
+            let mut buf = vec![0; len];
+            assert_eq!(
+                reader.seek(SeekFrom::Start(pos as u64)).unwrap(),
+                pos as u64
+            );
+            reader.read_exact(&mut buf).unwrap();
+            assert_eq!(buf, &data[pos..pos + len]);
+        }
+
+        assert_eq!(
+            reader.seek(SeekFrom::End(-4)).unwrap(),
+            data.len() as u64 - 4
+        );
+        assert_eq!(
+            reader.seek(SeekFrom::Current(-4)).unwrap(),
+            data.len() as u64 - 8
+        );
+        let mut rest = Vec::new();
+        reader.read_to_end(&mut rest).unwrap();
+        assert_eq!(rest, &data[data.len() - 8..]);
+
+        assert_eq!(
+            reader.seek(SeekFrom::End(10)).unwrap(),
+            data.len() as u64 + 10
+        );
+        assert_eq!(reader.read(&mut [0; 4]).unwrap(), 0);
+        assert_eq!(
+            reader.seek(SeekFrom::Current(-1 << 20)).unwrap_err().kind(),
+            ErrorKind::InvalidInput
+        );
+    }
+
+    #[test]
+    fn reads_a_list_of_chunks() {
+        let data = data();
+        let mut store = MemoryStore::<Fnv>::new();
+        let chunks: Vec<_> = CFG
+            .spans(&data)
+            .map(|chunk| ChunkRef {
+                digest: store.put(&chunk).unwrap().digest,
+                len: chunk.len() as u64,
+                level: chunk.level,
+            })
+            .collect();
+
+        let mut reader = TreeReader::new(Outline::from_chunks(chunks), &store).unwrap();
+        let mut restored = Vec::new();
+        reader.read_to_end(&mut restored).unwrap();
+        assert_eq!(restored, data);
+    }
+
+    #[test]
+    fn rejects_bad_chunks() {
+        let data = data();
+        let store = MemoryStore::<Fnv>::new();
+        let mut reader = TreeReader::new(outline(&data), &store).unwrap();
+        assert_eq!(
+            reader.read(&mut [0; 16]).unwrap_err().kind(),
+            ErrorKind::NotFound
+        );
+
+        let mut store = MemoryStore::<Fnv>::new();
+        for chunk in CFG.spans(&data) {
+            let mut corrupt = chunk.to_vec();
+            corrupt[0] ^= 1;
+            store
+                .insert(&chunk_digest::<Fnv>(&chunk), &corrupt)
+                .unwrap();
+        }
+        let mut reader = TreeReader::new(outline(&data), &store).unwrap();
+        assert_eq!(
+            reader.read(&mut [0; 16]).unwrap_err().kind(),
+            ErrorKind::InvalidData
+        );
+    }
+
+    #[test]
+    fn rejects_bad_outlines() {
+        let data = data();
+        let store = MemoryStore::<Fnv>::new();
+
+        let mut outline = outline(&data);
+        if let OutlineNode::Internal { len, .. } = &mut outline.root {
+            *len += 1;
+        } else {
+            panic!("the tree should have more than one leaf");
+        }
+
+        assert_eq!(
+            TreeReader::new(outline, &store).err().unwrap().kind(),
+            ErrorKind::InvalidData
+        );
+
+        // Lengths that only add up when they wrap around.
+        let chunks: Vec<_> = [1 << 63, 1 << 63]
+            .iter()
+            .map(|&len| ChunkRef {
+                digest: Fnv::digest(b"chunk"),
+                len,
+                level: None,
+            })
+            .collect();
+        let outline = Outline {
+            root: OutlineNode::Leaf {
+                digest: leaf_digest::<Fnv>(chunks.iter().map(|chunk| &chunk.digest)),
+                len: 0,
+                chunks: chunks.into_boxed_slice(),
+            },
+        };
+        assert_eq!(
+            TreeReader::new(outline, &store).err().unwrap().kind(),
+            ErrorKind::InvalidData
+        );
+    }
+}
@@ -0,0 +1,76 @@
+//! Checks that chunking an async reader or a stream of buffers gives the same
+//! chunks as chunking the slice, and that errors of the stream come through.
+
+#![cfg(feature = "futures")]
+
+mod common;
+
+use futures::executor::block_on;
+use futures::io::Cursor;
+use futures::stream::{self, StreamExt, TryStreamExt};
+use hashsplit::algorithms::Cp32;
+use hashsplit::config::DynConfig;
+use hashsplit::stream::AsyncReadChunks;
+
+type Chunks = Vec<(Vec<u8>, Option<u32>, u32)>;
+
+fn expected(cfg: DynConfig<Cp32>, data: &[u8]) -> Chunks {
+    cfg.spans(data)
+        .map(|chunk| (chunk.to_vec(), chunk.level, chunk.state))
+        .collect()
+}
+
+#[test]
+fn async_read_same_as_spans() {
+    let data = common::data();
+    let cfg = DynConfig::new(Cp32, 8, 64, 1024);
+
+    for buffer_size in [1, 100, 1024, 5000, 1 << 16] {
+        let reader = Cursor::new(&data);
+        let chunks: Chunks = block_on(
+            AsyncReadChunks::with_buffer_size(cfg.hasher, reader, cfg.limits(), buffer_size)
+                .map_ok(|chunk| (chunk.to_vec(), chunk.level, chunk.state))
+                .try_collect(),
+        )
+        .unwrap();
+
+        assert_eq!(chunks, expected(cfg, &data), "buffer of {}", buffer_size);
+    }
+}
+
+#[test]
+fn stream_same_as_spans() {
+    let data = common::data();
+    let cfg = DynConfig::new(Cp32, 8, 64, 1024);
+
+    for piece in [1, 333, 1024, 5000, 1 << 16] {
+        // Empty buffers between the pieces must not end the input.
+        let pieces = data
+            .chunks(piece)
+            .flat_map(|piece| [Ok::<_, ()>(piece), Ok(&[][..])]);
+        let chunks: Chunks = block_on(
+            cfg.stream_chunks(stream::iter(pieces))
+                .map_ok(|chunk| (chunk.to_vec(), chunk.level, chunk.state))
+                .try_collect(),
+        )
+        .unwrap();
+
+        assert_eq!(chunks, expected(cfg, &data), "pieces of {}", piece);
+    }
+}
+
+#[test]
+fn stream_passes_errors_through() {
+    let cfg = DynConfig::new(Cp32, 8, 64, 1024);
+    let pieces = stream::iter([Ok(&[0; 5000][..]), Err("broken"), Ok(&[0; 5000][..])]);
+
+    let results: Vec<_> = block_on(
+        cfg.stream_chunks(pieces)
+            .map_ok(|chunk| chunk.len())
+            .collect(),
+    );
+    let error = results.iter().position(Result::is_err).unwrap();
+
+    assert_eq!(results[error], Err("broken"));
+    assert!(results[..error].iter().all(Result::is_ok));
+}
@@ -0,0 +1,107 @@
+//! Checks that block-at-a-time hashing with `Thinned` gives the same results
+//! as hashing one byte at a time.
+
+#![cfg(feature = "alloc")]
+
+mod common;
+
+use common::{inputs, limits};
+use hashsplit::algorithms::rrs::Rrs;
+use hashsplit::algorithms::{Cp32, Rrs1};
+use hashsplit::config::DynConfig;
+use hashsplit::thin::Thinned;
+use hashsplit::{Leveled, Memoryless};
+
+use proptest::prelude::*;
+use std::fmt::Debug;
+
+fn same_as_sequence<Hash: Thinned<[u8; N]>, const N: usize>(
+    hasher: Hash,
+    state: Hash::State,
+    old_block: &[u8],
+    new_block: &[u8],
+) where
+    Hash::Checksum: PartialEq + Debug,
+    Hash::State: Clone + PartialEq + Debug,
+{
+    let mut block = [0; N];
+    block.copy_from_slice(&new_block[..N]);
+    let old_block = &old_block[..N];
+
+    assert_eq!(
+        hasher.process_block(state.clone(), old_block, &block),
+        hasher.process_sequence(state, old_block.iter().copied().zip(block.iter().copied())),
+    );
+}
+
+fn same_as_spans<Hash: Memoryless + Copy + Thinned<[u8; N]>, const N: usize>(
+    cfg: DynConfig<Hash>,
+    data: &[u8],
+) where
+    Hash::Checksum: Leveled,
+    Hash::State: Clone + PartialEq + Debug,
+{
+    let chunks = |chunks: &mut dyn Iterator<Item = hashsplit::chunk::ResumableChunk<'_, Hash>>| {
+        chunks
+            .map(|chunk| (chunk.len(), chunk.level, chunk.state))
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(
+        chunks(&mut cfg.thinned_spans::<N>(data)),
+        chunks(&mut cfg.spans(data))
+    );
+}
+
+fn blocks() -> impl Strategy<Value = (Vec<u8>, Vec<u8>)> {
+    (
+        proptest::collection::vec(any::<u8>(), 100),
+        proptest::collection::vec(any::<u8>(), 100),
+    )
+}
+
+proptest! {
+    #[test]
+    fn cp32_blocks(state in any::<u32>(), (old, new) in blocks()) {
+        same_as_sequence::<_, 1>(Cp32, state, &old, &new);
+        same_as_sequence::<_, 3>(Cp32, state, &old, &new);
+        same_as_sequence::<_, 16>(Cp32, state, &old, &new);
+        same_as_sequence::<_, 33>(Cp32, state, &old, &new);
+        same_as_sequence::<_, 100>(Cp32, state, &old, &new);
+    }
+
+    #[test]
+    fn rrs1_blocks(a in 0u32..65_536, b in 0u32..65_536, (old, new) in blocks()) {
+        same_as_sequence::<_, 1>(Rrs1::default(), (a, b), &old, &new);
+        same_as_sequence::<_, 3>(Rrs1::default(), (a, b), &old, &new);
+        same_as_sequence::<_, 16>(Rrs1::default(), (a, b), &old, &new);
+        same_as_sequence::<_, 64>(Rrs1::default(), (a, b), &old, &new);
+        same_as_sequence::<_, 100>(Rrs1::default(), (a, b), &old, &new);
+    }
+
+    #[test]
+    fn rrs_odd_modulus_blocks(a in 0u32..251, b in 0u32..251, (old, new) in blocks()) {
+        let hasher = Rrs::<251, 31, 7>;
+        same_as_sequence::<_, 1>(hasher, (a, b), &old, &new);
+        same_as_sequence::<_, 16>(hasher, (a, b), &old, &new);
+        same_as_sequence::<_, 100>(hasher, (a, b), &old, &new);
+    }
+
+    #[test]
+    fn cp32_spans((threshold, min, max) in limits(10, 600, 1000), data in inputs(4096)) {
+        let cfg = DynConfig::new(Cp32, threshold, min, max);
+        same_as_spans::<_, 1>(cfg, &data);
+        same_as_spans::<_, 8>(cfg, &data);
+        same_as_spans::<_, 64>(cfg, &data);
+        same_as_spans::<_, 100>(cfg, &data);
+    }
+
+    #[test]
+    fn rrs1_spans((threshold, min, max) in limits(10, 600, 1000), data in inputs(4096)) {
+        let cfg = DynConfig::new(Rrs1::default(), threshold, min, max);
+        same_as_spans::<_, 1>(cfg, &data);
+        same_as_spans::<_, 8>(cfg, &data);
+        same_as_spans::<_, 64>(cfg, &data);
+        same_as_spans::<_, 100>(cfg, &data);
+    }
+}
@@ -0,0 +1,147 @@
+//! Checks that `StreamingTreeBuilder` emits exactly the nodes of the tree
+//! built by `TreeBuilder`, children first, and that the digests of chunks and
+//! nodes are kept apart.
+
+#![cfg(feature = "alloc")]
+
+mod common;
+
+use common::{data, Fnv};
+use hashsplit::algorithms::Cp32;
+use hashsplit::chunk::{ResumableChunk, StreamingTreeBuilder, TreeBuilder, TreeNode};
+use hashsplit::digest::{chunk_digest, leaf_digest};
+use hashsplit::Config;
+
+use proptest::prelude::*;
+
+const THRESHOLD: u32 = 4;
+
+/// The height, digest, length and number of children of every node below and
+/// including `node`, in post-order. Returns the height and length of `node`.
+fn post_order(
+    node: &TreeNode<'_, Cp32, Fnv>,
+    out: &mut Vec<(u32, [u8; 8], u64, usize)>,
+) -> (u32, u64) {
+    let (height, len, children) = match node {
+        TreeNode::Internal { children, .. } => {
+            let below: Vec<_> = children
+                .iter()
+                .map(|child| post_order(child, out))
+                .collect();
+
+            (
+                below[0].0 + 1,
+                below.iter().map(|&(_, len)| len).sum(),
+                children.len(),
+            )
+        }
+        TreeNode::Leaf { chunks, .. } => (
+            0,
+            chunks.iter().map(|chunk| chunk.len() as u64).sum(),
+            chunks.len(),
+        ),
+    };
+    out.push((height, *node.digest(), len, children));
+
+    (height, len)
+}
+
+fn chunks(shape: &[(usize, Option<u32>)]) -> Vec<ResumableChunk<'static, Cp32>> {
+    shape
+        .iter()
+        .enumerate()
+        .map(|(i, &(len, level))| {
+            let mut chunk = ResumableChunk::new(vec![i as u8; len], 0);
+            chunk.level = level;
+            chunk
+        })
+        .collect()
+}
+
+fn check(shape: &[(usize, Option<u32>)]) {
+    let mut builder = TreeBuilder::<_, Fnv>::new(THRESHOLD);
+    builder.extend(chunks(shape));
+    let tree = builder.finish();
+
+    let mut emitted = Vec::new();
+    let mut streaming = StreamingTreeBuilder::<Fnv, _>::new(THRESHOLD, |node| {
+        emitted.push((node.height, node.digest, node.len, node.children));
+    });
+    streaming.extend(chunks(shape));
+    let root = streaming.finish();
+
+    let mut expected = Vec::new();
+    if let Some(tree) = &tree {
+        post_order(&tree.root, &mut expected);
+    }
+    assert_eq!(emitted, expected);
+    assert_eq!(
+        root.map(|node| node.digest),
+        tree.map(|tree| *tree.digest())
+    );
+}
+
+#[test]
+fn single_child_chain_above_root() {
+    // The first chunk closes the leaf and three nodes above it, which would
+    // each have a single child and be replaced by the leaf.
+    check(&[(5, Some(THRESHOLD + 3))]);
+    check(&[(5, None), (3, Some(THRESHOLD + 3))]);
+    // Two leaves under one node, wrapped twice.
+    check(&[(5, Some(THRESHOLD + 1)), (3, Some(THRESHOLD + 3))]);
+}
+
+#[test]
+fn chunk_digests_differ_from_leaf_digests() {
+    let mut builder = TreeBuilder::<Cp32, Fnv>::new(THRESHOLD);
+    builder.push(ResumableChunk::new(&b"first"[..], 0));
+    builder.push(ResumableChunk::new(&b"second"[..], 0));
+    let leaf = *builder.finish().unwrap().digest();
+
+    let digests = [
+        chunk_digest::<Fnv>(b"first"),
+        chunk_digest::<Fnv>(b"second"),
+    ];
+    assert_eq!(leaf, leaf_digest::<Fnv>(&digests));
+
+    // The bytes a leaf over those chunks hashes, as a chunk of their own.
+    let mut forged = vec![0];
+    for digest in &digests {
+        forged.extend(digest);
+    }
+    assert_ne!(chunk_digest::<Fnv>(&forged), leaf);
+}
+
+#[test]
+fn tree_of_spans() {
+    let data = data();
+    let cfg: Config<Cp32, 8, 64, 1024> = Default::default();
+    let tree = |data: &[u8]| {
+        let mut builder = TreeBuilder::<_, Fnv>::new(8);
+        builder.extend(cfg.spans(data));
+        let tree = builder.finish().unwrap();
+        let (height, len) = post_order(&tree.root, &mut Vec::new());
+
+        (height, len, *tree.digest())
+    };
+
+    let (height, len, digest) = tree(&data);
+    assert!(height > 0);
+    assert_eq!(len, data.len() as u64);
+
+    let mut edited = data.clone();
+    edited[1000] ^= 1;
+    assert_ne!(tree(&edited).2, digest);
+}
+
+proptest! {
+    #[test]
+    fn same_as_tree_builder(
+        shape in proptest::collection::vec(
+            (1usize..4, proptest::option::of(0u32..THRESHOLD + 4)),
+            0..60,
+        )
+    ) {
+        check(&shape);
+    }
+}
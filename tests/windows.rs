@@ -0,0 +1,226 @@
+//! Checks that the rolling updates of RRS, Bozo32 and Buzhash agree with
+//! hashing the window from scratch, exhaustively for small windows.
+
+use hashsplit::algorithms::buzhash::{Buzhash, Standard, Table};
+use hashsplit::algorithms::{bozo32, rrs, rrs64, Bozo32, Buzhash32, Rrs1, Rrs64};
+use hashsplit::iter::Rolling;
+use hashsplit::{Hasher, WINDOW_SIZE};
+
+use std::fmt::Debug;
+
+/// Checks every window of `WINDOW` bytes against every incoming byte.
+fn exhaustive<const WINDOW: usize, Checksum: PartialEq + Debug, State: PartialEq + Debug>(
+    hash_window: impl Fn(&[u8; WINDOW]) -> (Checksum, State),
+    process_byte: impl Fn(State, u8, u8) -> (Checksum, State),
+) {
+    let count = 1usize << (8 * WINDOW);
+    for index in 0..count {
+        let mut window = [0u8; WINDOW];
+        for (i, byte) in window.iter_mut().enumerate() {
+            *byte = (index >> (8 * i)) as u8;
+        }
+
+        for new_byte in 0..=255 {
+            let mut next = [0u8; WINDOW];
+            next[..WINDOW - 1].copy_from_slice(&window[1..]);
+            next[WINDOW - 1] = new_byte;
+
+            assert_eq!(
+                process_byte(hash_window(&window).1, window[0], new_byte),
+                hash_window(&next),
+                "{:?} then {}",
+                window,
+                new_byte
+            );
+        }
+    }
+}
+
+#[test]
+fn rrs1_small_windows() {
+    exhaustive::<1, _, _>(
+        rrs::hash_window::<65_536, 31, 1>,
+        rrs::process_byte_freestanding::<65_536, 31, 1>,
+    );
+    exhaustive::<2, _, _>(
+        rrs::hash_window::<65_536, 31, 2>,
+        rrs::process_byte_freestanding::<65_536, 31, 2>,
+    );
+}
+
+#[test]
+fn rrs_odd_modulus_small_windows() {
+    exhaustive::<1, _, _>(
+        rrs::hash_window::<251, 31, 1>,
+        rrs::process_byte_freestanding::<251, 31, 1>,
+    );
+    exhaustive::<2, _, _>(
+        rrs::hash_window::<251, 31, 2>,
+        rrs::process_byte_freestanding::<251, 31, 2>,
+    );
+}
+
+#[test]
+fn rrs64_small_windows() {
+    exhaustive::<1, _, _>(
+        rrs64::hash_window::<{ 1 << 32 }, 31, 1>,
+        rrs64::process_byte_freestanding::<{ 1 << 32 }, 31, 1>,
+    );
+    exhaustive::<2, _, _>(
+        rrs64::hash_window::<{ 1 << 32 }, 31, 2>,
+        rrs64::process_byte_freestanding::<{ 1 << 32 }, 31, 2>,
+    );
+    exhaustive::<2, _, _>(
+        rrs64::hash_window::<4_294_967_291, 31, 2>,
+        rrs64::process_byte_freestanding::<4_294_967_291, 31, 2>,
+    );
+}
+
+#[test]
+fn bozo32_small_windows() {
+    exhaustive::<1, _, _>(
+        bozo32::hash_window::<1>,
+        bozo32::process_byte_freestanding::<1>,
+    );
+    exhaustive::<2, _, _>(
+        bozo32::hash_window::<2>,
+        bozo32::process_byte_freestanding::<2>,
+    );
+}
+
+fn input(len: usize) -> Vec<u8> {
+    let mut x: u64 = 0x2545_f491_4f6c_dd1d;
+
+    (0..len)
+        .map(|_| {
+            x ^= x << 13;
+            x ^= x >> 7;
+            x ^= x << 17;
+            (x >> 56) as u8
+        })
+        .collect()
+}
+
+/// The last `N` bytes of `data[..end]`, padded with zeros in front.
+fn window<const N: usize>(data: &[u8], end: usize) -> [u8; N] {
+    let mut window = [0; N];
+    let begin = end.saturating_sub(N);
+    window[N - (end - begin)..].copy_from_slice(&data[begin..end]);
+
+    window
+}
+
+#[test]
+fn rrs1_full_window() {
+    let data = input(4096);
+    for (i, sum) in Rolling::start(Rrs1::default(), data.iter().copied()).enumerate() {
+        let (expected, _) = rrs::hash_window::<65_536, 31, WINDOW_SIZE>(&window(&data, i + 1));
+        assert_eq!(sum, expected, "at {}", i);
+    }
+}
+
+/// RRS1 sums of full windows, worked out from the definition in the
+/// specification. With `x_i = i`, `a = 2016 + 64 * 31 = 4000` and
+/// `b = 42_624`; with every byte `0xff`, `a = 64 * 286 mod 2^16 = 18_304` and
+/// `b = 2080 * 286 mod 2^16 = 5056`.
+#[test]
+fn rrs1_known_windows() {
+    let last = |data: &[u8]| Rolling::start(Rrs1::default(), data.iter().copied()).last();
+
+    let ramp: Vec<u8> = (0..64).collect();
+    assert_eq!(last(&ramp), Some(0x0fa0_a680));
+    assert_eq!(last(&[0xff; 64]), Some(0x4780_13c0));
+
+    // Bytes that have left the window make no difference.
+    let mut longer = input(1000);
+    longer.extend_from_slice(&ramp);
+    assert_eq!(last(&longer), Some(0x0fa0_a680));
+}
+
+#[test]
+fn rrs64_full_window() {
+    let data = input(4096);
+    for (i, sum) in Rolling::start(Rrs64::default(), data.iter().copied()).enumerate() {
+        let (expected, _) =
+            rrs64::hash_window::<{ 1 << 32 }, 31, WINDOW_SIZE>(&window(&data, i + 1));
+        assert_eq!(sum, expected, "at {}", i);
+    }
+}
+
+#[test]
+fn bozo32_full_window() {
+    let data = input(4096);
+    for (i, sum) in Rolling::start(Bozo32, data.iter().copied()).enumerate() {
+        let (expected, _) = bozo32::hash_window::<WINDOW_SIZE>(&window(&data, i + 1));
+        assert_eq!(sum, expected, "at {}", i);
+    }
+}
+
+/// Buzhash of a window from scratch: the XOR of the table entry of every byte,
+/// rotated once for every byte after it.
+fn buzhash_window(window: &[u8]) -> u32 {
+    window.iter().enumerate().fold(0, |sum, (i, &byte)| {
+        sum ^ Standard::TABLE[byte as usize].rotate_left((window.len() - 1 - i) as u32)
+    })
+}
+
+#[test]
+fn buzhash32_full_window() {
+    let data = input(4096);
+    for (i, sum) in Rolling::start(Buzhash32::default(), data.iter().copied()).enumerate() {
+        let expected = buzhash_window(&window::<WINDOW_SIZE>(&data, i + 1));
+        assert_eq!(sum, expected, "at {}", i);
+    }
+
+    let rolling = Rolling::start(Buzhash::<Standard, 48>::default(), data.iter().copied());
+    for (i, sum) in rolling.enumerate() {
+        assert_eq!(sum, buzhash_window(&window::<48>(&data, i + 1)), "at {}", i);
+    }
+}
+
+/// Buzhash32 sums of full windows with the standard table. Every byte of a
+/// 64-byte window is rotated twice by each multiple of 32 bits, so a window of
+/// one repeated byte hashes to zero.
+#[test]
+fn buzhash32_known_windows() {
+    let last = |data: &[u8]| Rolling::start(Buzhash32::default(), data.iter().copied()).last();
+
+    let ramp: Vec<u8> = (0..64).collect();
+    assert_eq!(last(&ramp), Some(0xe2e5_1952));
+    assert_eq!(last(&[0xff; 64]), Some(0));
+
+    let mut longer = input(1000);
+    longer.extend_from_slice(&ramp);
+    assert_eq!(last(&longer), Some(0xe2e5_1952));
+
+    let last48 = Rolling::start(Buzhash::<Standard, 48>::default(), 0..48).last();
+    assert_eq!(last48, Some(0x141f_c7c0));
+}
+
+/// Reports the byte leaving the window.
+struct Leaving;
+
+impl Hasher for Leaving {
+    type Checksum = u32;
+
+    type State = ();
+
+    type Ring = [u8; WINDOW_SIZE];
+
+    const INITIAL_STATE: () = ();
+
+    fn process_byte(&self, _: (), old_byte: u8, _: u8) -> (u32, ()) {
+        (old_byte.into(), ())
+    }
+}
+
+#[test]
+fn leaving_byte() {
+    let data = input(200);
+    let sums: Vec<_> = Rolling::start(Leaving, data.iter().copied()).collect();
+
+    assert!(sums[..WINDOW_SIZE].iter().all(|&sum| sum == 0));
+    for (i, &sum) in sums[WINDOW_SIZE..].iter().enumerate() {
+        assert_eq!(sum, data[i].into(), "at {}", i + WINDOW_SIZE);
+    }
+}